@@ -0,0 +1,232 @@
+use std::fmt;
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+/// Result of a puzzle part. Puzzles answer with signed or unsigned numbers of
+/// varying size and sometimes with text, so the runner never has to cast.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    U128(u128),
+    Big(BigUint),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // pad so the answer can be aligned in tables like any other value
+        match self {
+            Answer::Int(n) => n.fmt(f),
+            Answer::UInt(n) => n.fmt(f),
+            Answer::U128(n) => n.fmt(f),
+            Answer::Big(n) => f.pad(&n.to_string()),
+            Answer::Text(s) => f.pad(s),
+        }
+    }
+}
+
+/// Answers are submitted as text, so two answers are equal when they print the same.
+/// This also lets `Int(5)` equal `UInt(5)` or `Text("5")`.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::UInt(a), Answer::UInt(b)) => a == b,
+            (Answer::U128(a), Answer::U128(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+macro_rules! answer_from {
+    ($variant:ident: $target:ty => $($source:ty),*) => {
+        $(impl From<$source> for Answer {
+            fn from(n: $source) -> Answer {
+                Answer::$variant(n as $target)
+            }
+        })*
+    };
+}
+
+answer_from!(Int: i64 => i8, i16, i32, i64, isize);
+answer_from!(UInt: u64 => u8, u16, u32, u64, usize);
+answer_from!(U128: u128 => u128);
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Answer {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Answer {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+const LIMB_BASE: u64 = 1_000_000_000;
+
+/// Arbitrary precision unsigned integer for answers that overflow `u128`.
+/// Stored as little endian base 10^9 limbs which keeps printing trivial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: vec![] }
+    }
+
+    fn normalize(mut self) -> BigUint {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut n: u128) -> BigUint {
+        let mut limbs = vec![];
+        while n > 0 {
+            limbs.push((n % LIMB_BASE as u128) as u32);
+            n /= LIMB_BASE as u128;
+        }
+        BigUint { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> BigUint {
+        BigUint::from(n as u128)
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BigUint, String> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("invalid integer `{}`", s));
+        }
+        let limbs = s
+            .as_bytes()
+            .rchunks(9)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap().parse::<u32>().unwrap())
+            .collect();
+        Ok(BigUint { limbs }.normalize())
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((last, rest)) => {
+                write!(f, "{}", last)?;
+                rest.iter().rev().try_for_each(|limb| write!(f, "{:09}", limb))
+            }
+        }
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0;
+        for idx in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = *self.limbs.get(idx).unwrap_or(&0) as u64 + *other.limbs.get(idx).unwrap_or(&0) as u64 + carry;
+            limbs.push((sum % LIMB_BASE) as u32);
+            carry = sum / LIMB_BASE;
+        }
+        limbs.push(carry as u32);
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let cur = limbs[i + j] + a as u64 * b as u64 + carry;
+                limbs[i + j] = cur % LIMB_BASE;
+                carry = cur / LIMB_BASE;
+            }
+            limbs[i + other.limbs.len()] += carry;
+        }
+        BigUint { limbs: limbs.into_iter().map(|l| l as u32).collect() }.normalize()
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        &self + &other
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        &self * &other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_eq() {
+        assert_eq!(Answer::from(5), Answer::from(5u64));
+        assert_eq!(Answer::from(-5), Answer::from("-5"));
+        assert_eq!(Answer::from(u128::MAX), Answer::from(BigUint::from(u128::MAX)));
+        assert_ne!(Answer::from(5), Answer::from(6usize));
+        assert_ne!(Answer::from("1,2"), Answer::from("1,3"));
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(format!("{:<5}|", Answer::from(42)), "42   |");
+        assert_eq!(format!("{:<5}|", Answer::from("a,b")), "a,b  |");
+    }
+
+    #[test]
+    fn test_big_uint() {
+        let a = BigUint::from(u128::MAX);
+        assert_eq!(a.to_string(), u128::MAX.to_string());
+        assert_eq!((&a + &BigUint::from(1u64)).to_string(), "340282366920938463463374607431768211456");
+        assert_eq!((&a * &a).to_string(), "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+        assert_eq!("000123000000000".parse::<BigUint>().unwrap(), BigUint::from(123000000000u64));
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert!("12a".parse::<BigUint>().is_err());
+    }
+}
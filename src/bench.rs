@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::cli::Part;
+use crate::error;
+use crate::runner::{self, DayResult};
+use crate::solution::Day;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchOptions {
+    /// Fixed number of measured runs, otherwise runs are taken until `time` is used up.
+    pub runs: Option<usize>,
+    pub time: Duration,
+    pub warmup: usize,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    /// Allowed slowdown of the median against the baseline, in percent.
+    pub threshold: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> BenchOptions {
+        BenchOptions {
+            runs: None,
+            time: Duration::from_secs(1),
+            warmup: 3,
+            baseline: None,
+            save_baseline: None,
+            threshold: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+    Total,
+}
+
+impl Phase {
+    const ALL: [Phase; 4] = [Phase::Parse, Phase::Part1, Phase::Part2, Phase::Total];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+            Phase::Total => "total",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    fn time(&self, result: &DayResult) -> Duration {
+        match self {
+            Phase::Parse => result.parse_time,
+            Phase::Part1 => result.part1_time,
+            Phase::Part2 => result.part2_time,
+            Phase::Total => result.total_time(),
+        }
+    }
+
+    fn selected(&self, part: Option<Part>) -> bool {
+        !matches!((self, part), (Phase::Part1, Some(Part::Two)) | (Phase::Part2, Some(Part::One)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarize");
+        samples.sort();
+        let runs = samples.len();
+        let nanos = samples.iter().map(|d| d.as_nanos() as f64).collect::<Vec<_>>();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+        // nearest rank percentile
+        let p95_rank = ((0.95 * runs as f64).ceil() as usize).max(1);
+        Stats {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_nanos(mean as u64),
+            p95: samples[p95_rank - 1],
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+/// Median timings per day and phase from an earlier run.
+/// Stored as one `day phase median_ns` line per entry so it can be diffed and edited by hand.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    medians: HashMap<(u32, Phase), Duration>,
+}
+
+impl Baseline {
+    pub fn load(path: &PathBuf) -> io::Result<Baseline> {
+        let text = fs::read_to_string(path)?;
+        Baseline::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn parse(text: &str) -> Result<Baseline, String> {
+        let mut medians = HashMap::new();
+        for (idx, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let invalid = || format!("invalid baseline entry on line {}: `{}`", idx + 1, line);
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [day, phase, nanos] = fields[..] else {
+                return Err(invalid());
+            };
+            let day = day.parse::<u32>().map_err(|_| invalid())?;
+            let phase = Phase::from_name(phase).ok_or_else(invalid)?;
+            let nanos = nanos.parse::<u64>().map_err(|_| invalid())?;
+            medians.insert((day, phase), Duration::from_nanos(nanos));
+        }
+        Ok(Baseline { medians })
+    }
+
+    pub fn save(&self, path: &PathBuf) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    fn get(&self, day: u32, phase: Phase) -> Option<Duration> {
+        self.medians.get(&(day, phase)).copied()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries = self.medians.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|((day, phase), _)| (*day, Phase::ALL.iter().position(|p| p == phase)));
+        for ((day, phase), median) in entries {
+            writeln!(f, "{:02} {} {}", day, phase.name(), median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// Relative change of `median` against the baseline, in percent.
+fn change(median: Duration, baseline: Duration) -> f64 {
+    (median.as_nanos() as f64 / baseline.as_nanos().max(1) as f64 - 1.0) * 100.0
+}
+
+pub struct DayBench {
+    pub day: u32,
+    pub stats: Vec<(Phase, Stats)>,
+}
+
+/// Warms up and then repeatedly runs a day, one run at a time so timings don't interfere.
+pub fn bench_day(day: &Day, input: &[u8], part: Option<Part>, options: &BenchOptions) -> error::Result<DayBench> {
+    for _ in 0..options.warmup {
+        runner::run_day(day, input, part)?;
+    }
+    let mut results = vec![];
+    let start = Instant::now();
+    loop {
+        results.push(runner::run_day(day, input, part)?);
+        let done = match options.runs {
+            Some(runs) => results.len() >= runs,
+            None => start.elapsed() >= options.time,
+        };
+        if done {
+            break;
+        }
+    }
+    let stats = Phase::ALL
+        .into_iter()
+        .filter(|phase| phase.selected(part))
+        .map(|phase| {
+            let mut samples = results.iter().map(|r| phase.time(r)).collect::<Vec<_>>();
+            (phase, Stats::from_samples(&mut samples))
+        })
+        .collect();
+    Ok(DayBench { day: day.day, stats })
+}
+
+pub fn print_header() {
+    println!(
+        "{0: <3} | {1: <5} | {2: >7} | {3: >10} | {4: >10} | {5: >10} | {6: >10} | {7: >10} | Baseline",
+        "Day", "Phase", "Runs", "Min μs", "Median μs", "Mean μs", "P95 μs", "Std dev μs"
+    );
+}
+
+/// Prints the stats of a day and returns whether any phase regressed past the threshold.
+pub fn print_day(bench: &DayBench, baseline: Option<&Baseline>, threshold: f64) -> bool {
+    let micros = |d: Duration| format!("{:.1}", d.as_nanos() as f64 / 1000.0);
+    let mut regressed = false;
+    for (phase, stats) in &bench.stats {
+        let comparison = match baseline.and_then(|b| b.get(bench.day, *phase)) {
+            Some(base) => {
+                let change = change(stats.median, base);
+                if change > threshold {
+                    regressed = true;
+                    format!("{:+.1}% REGRESSION", change)
+                } else {
+                    format!("{:+.1}%", change)
+                }
+            }
+            None => String::new(),
+        };
+        println!(
+            "{0:<3} | {1:<5} | {2:>7} | {3:>10} | {4:>10} | {5:>10} | {6:>10} | {7:>10} | {8}",
+            format!("{:02}", bench.day),
+            phase.name(),
+            stats.runs,
+            micros(stats.min),
+            micros(stats.median),
+            micros(stats.mean),
+            micros(stats.p95),
+            micros(stats.std_dev),
+            comparison
+        );
+    }
+    regressed
+}
+
+pub fn to_baseline(benches: &[DayBench]) -> Baseline {
+    let medians = benches
+        .iter()
+        .flat_map(|b| b.stats.iter().map(|(phase, stats)| ((b.day, *phase), stats.median)))
+        .collect();
+    Baseline { medians }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&s| Duration::from_micros(s)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&mut micros(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1414));
+
+        let stats = Stats::from_samples(&mut micros(&[4, 1, 3, 2]));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let text = "01 parse 1500\n01 total 20000\n06 part2 7\n";
+        let baseline = Baseline::parse(text).unwrap();
+        assert_eq!(baseline.get(6, Phase::Part2), Some(Duration::from_nanos(7)));
+        assert_eq!(baseline.get(6, Phase::Part1), None);
+        assert_eq!(baseline.to_string(), text);
+        assert!(Baseline::parse("01 total").is_err());
+        assert!(Baseline::parse("01 phase 10").is_err());
+    }
+
+    #[test]
+    fn test_change() {
+        assert_eq!(change(Duration::from_micros(110), Duration::from_micros(100)).round(), 10.0);
+        assert_eq!(change(Duration::from_micros(50), Duration::from_micros(100)).round(), -50.0);
+    }
+}
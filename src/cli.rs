@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::bench::BenchOptions;
+use crate::report::Format;
+
+pub const USAGE: &str = "\
+Usage: adv-code-2024 [OPTIONS] [DAYS]...
+       adv-code-2024 new-day <DAY> [TITLE]...
+
+DAYS is a comma separated list of days or ranges, e.g. `6`, `1-5` or `1,3,7-9`.
+All days are run when no days are given.
+
+`new-day` creates src/days/dayNN.rs from a template, registers it in src/days/mod.rs
+and creates an empty input/NN.txt.
+
+Options:
+  -p, --part <1|2>          only run the given part
+  -i, --input [DAY=]PATH    read the input for DAY from PATH instead of input/NN.txt,
+                            use `-` for stdin. DAY may be omitted when a single day is selected
+  -v, --verify              check answers against the `.answers` file next to each input
+                            and fail if any answer is wrong
+  -f, --format <FORMAT>     results format: text (default), json, csv or markdown
+      --parallel            run the days on a thread pool and report the wall clock time
+  -h, --help                print this message
+
+Benchmarking:
+  -b, --bench               run each day repeatedly and report timing statistics
+      --runs <N>            measure exactly N runs per day instead of using a time budget
+      --bench-time <SECS>   time budget per day (default 1)
+      --warmup <N>          unmeasured runs before measuring (default 3)
+      --baseline <PATH>     compare medians against a saved baseline
+      --save-baseline <PATH>
+                            save the measured medians as a baseline
+      --threshold <PCT>     slowdown against the baseline reported as a regression (default 10)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn for_day(day: u32) -> InputSource {
+        InputSource::File(PathBuf::from(format!("input/{:02}.txt", day)))
+    }
+
+    /// Reads the whole input up front so that solver timings don't include I/O.
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        match self {
+            InputSource::File(path) => fs::read(path),
+            InputSource::Stdin => {
+                let mut input = vec![];
+                io::stdin().read_to_end(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Run(Options),
+    NewDay { day: u32, title: String },
+}
+
+impl Command {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I, available: &[u32]) -> Result<Command, String> {
+        let mut args = args.into_iter().peekable();
+        if args.next_if(|arg| arg == "new-day").is_none() {
+            return Options::parse(args, available).map(Command::Run);
+        }
+        let day = parse_value("new-day", args.next())?;
+        let title = match args.collect::<Vec<_>>().join(" ") {
+            title if title.is_empty() => format!("Day {}", day),
+            title => title,
+        };
+        Ok(Command::NewDay { day, title })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub days: Vec<u32>,
+    pub part: Option<Part>,
+    pub format: Format,
+    pub verify: bool,
+    pub parallel: bool,
+    pub help: bool,
+    pub bench: Option<BenchOptions>,
+    inputs: HashMap<u32, InputSource>,
+}
+
+impl Options {
+    /// Parses the command line arguments (without the program name).
+    /// `available` lists the days that have a solution and is used when no days are given.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I, available: &[u32]) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut unkeyed_input = None;
+        let mut bench = BenchOptions::default();
+        let mut bench_flag = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "-p" | "--part" => {
+                    let value = args.next().ok_or("--part requires a value")?;
+                    options.part = match value.as_str() {
+                        "1" => Some(Part::One),
+                        "2" => Some(Part::Two),
+                        _ => return Err(format!("invalid part `{}`, expected 1 or 2", value)),
+                    };
+                }
+                "-i" | "--input" => {
+                    let value = args.next().ok_or("--input requires a value")?;
+                    match value.split_once('=') {
+                        Some((day, path)) => {
+                            let day = parse_day(day, available)?;
+                            options.inputs.insert(day, parse_source(path));
+                        }
+                        None => unkeyed_input = Some(parse_source(&value)),
+                    }
+                }
+                "-v" | "--verify" => options.verify = true,
+                "--parallel" => options.parallel = true,
+                "-f" | "--format" => options.format = args.next().ok_or("--format requires a value")?.parse()?,
+                "-b" | "--bench" => options.bench = Some(BenchOptions::default()),
+                "--runs" => {
+                    bench.runs = Some(parse_value(&arg, args.next()).and_then(|runs| match runs {
+                        0 => Err("--runs must be at least 1".to_string()),
+                        runs => Ok(runs),
+                    })?);
+                    bench_flag = Some(arg);
+                }
+                "--bench-time" => {
+                    let secs: f64 = parse_value(&arg, args.next())?;
+                    bench.time = Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid --bench-time `{}`", secs))?;
+                    bench_flag = Some(arg);
+                }
+                "--warmup" => {
+                    bench.warmup = parse_value(&arg, args.next())?;
+                    bench_flag = Some(arg);
+                }
+                "--baseline" => {
+                    bench.baseline = Some(parse_value(&arg, args.next())?);
+                    bench_flag = Some(arg);
+                }
+                "--save-baseline" => {
+                    bench.save_baseline = Some(parse_value(&arg, args.next())?);
+                    bench_flag = Some(arg);
+                }
+                "--threshold" => {
+                    bench.threshold = parse_value(&arg, args.next())?;
+                    bench_flag = Some(arg);
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+                _ => options.days.extend(parse_days(&arg, available)?),
+            }
+        }
+
+        match (&options.bench, bench_flag) {
+            (Some(_), _) if options.format != Format::Text => return Err("--format is not supported with --bench".to_string()),
+            (Some(_), _) if options.parallel => return Err("--bench always runs days one at a time, --parallel is not supported".to_string()),
+            (Some(_), _) => options.bench = Some(bench),
+            (None, Some(flag)) => return Err(format!("{} requires --bench", flag)),
+            (None, None) => {}
+        }
+
+        if options.days.is_empty() {
+            options.days = available.to_vec();
+        }
+        options.days.sort();
+        options.days.dedup();
+
+        if let Some(source) = unkeyed_input {
+            match options.days[..] {
+                [day] => {
+                    options.inputs.insert(day, source);
+                }
+                _ => return Err("--input without DAY= requires exactly one selected day".to_string()),
+            }
+        }
+        if options.inputs.values().filter(|&s| *s == InputSource::Stdin).count() > 1 {
+            return Err("only one day can read its input from stdin".to_string());
+        }
+        Ok(options)
+    }
+
+    pub fn input_for(&self, day: u32) -> InputSource {
+        self.inputs.get(&day).cloned().unwrap_or_else(|| InputSource::for_day(day))
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    value.parse().map_err(|_| format!("invalid value `{}` for {}", value, flag))
+}
+
+fn parse_source(path: &str) -> InputSource {
+    if path == "-" {
+        InputSource::Stdin
+    } else {
+        InputSource::File(PathBuf::from(path))
+    }
+}
+
+fn parse_day(s: &str, available: &[u32]) -> Result<u32, String> {
+    match s.trim().parse::<u32>() {
+        Ok(day) if available.contains(&day) => Ok(day),
+        Ok(day) => Err(format!("day {} has no solution", day)),
+        Err(_) => Err(format!("invalid day `{}`", s)),
+    }
+}
+
+fn parse_days(spec: &str, available: &[u32]) -> Result<Vec<u32>, String> {
+    let mut days = vec![];
+    for item in spec.split(',').filter(|s| !s.is_empty()) {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let start = parse_day(start, available)?;
+                let end = parse_day(end, available)?;
+                if start > end {
+                    return Err(format!("invalid day range `{}`", item));
+                }
+                days.extend(available.iter().filter(|&d| (start..=end).contains(d)));
+            }
+            None => days.push(parse_day(item, available)?),
+        }
+    }
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(String::from), &(1..=11).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_new_day() {
+        let command = Command::parse(["new-day", "12", "Garden", "Groups"].map(String::from), &[1]).unwrap();
+        assert!(matches!(command, Command::NewDay { day: 12, title } if title == "Garden Groups"));
+        let command = Command::parse(["new-day", "12"].map(String::from), &[1]).unwrap();
+        assert!(matches!(command, Command::NewDay { day: 12, title } if title == "Day 12"));
+        assert!(Command::parse(["new-day"].map(String::from), &[1]).is_err());
+        assert!(matches!(Command::parse(["1"].map(String::from), &[1]), Ok(Command::Run(_))));
+    }
+
+    #[test]
+    fn test_days() {
+        assert_eq!(parse("").unwrap().days, (1..=11).collect::<Vec<_>>());
+        assert_eq!(parse("6").unwrap().days, vec![6]);
+        assert_eq!(parse("1-3,7 9 3").unwrap().days, vec![1, 2, 3, 7, 9]);
+        assert!(parse("12").is_err());
+        assert!(parse("5-2").is_err());
+        assert!(parse("x").is_err());
+    }
+
+    #[test]
+    fn test_part() {
+        assert_eq!(parse("-p 2").unwrap().part, Some(Part::Two));
+        assert_eq!(parse("").unwrap().part, None);
+        assert!(parse("--part 3").is_err());
+    }
+
+    #[test]
+    fn test_inputs() {
+        let options = parse("6 -i other.txt").unwrap();
+        assert_eq!(options.input_for(6), InputSource::File(PathBuf::from("other.txt")));
+
+        let options = parse("--input 2=- --input 3=a.txt").unwrap();
+        assert_eq!(options.input_for(1), InputSource::for_day(1));
+        assert_eq!(options.input_for(2), InputSource::Stdin);
+        assert_eq!(options.input_for(3), InputSource::File(PathBuf::from("a.txt")));
+
+        assert!(parse("1-2 -i a.txt").is_err());
+        assert!(parse("-i 1=- -i 2=-").is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(parse("").unwrap().format, Format::Text);
+        assert_eq!(parse("-f json").unwrap().format, Format::Json);
+        assert_eq!(parse("--format markdown").unwrap().format, Format::Markdown);
+        assert!(parse("--format xml").is_err());
+        assert!(parse("--format csv --bench").is_err());
+        assert!(parse("--format csv --verify").unwrap().verify);
+    }
+
+    #[test]
+    fn test_parallel() {
+        assert!(!parse("").unwrap().parallel);
+        assert!(parse("1-5 --parallel").unwrap().parallel);
+    }
+
+    #[test]
+    fn test_bench() {
+        assert_eq!(parse("").unwrap().bench, None);
+        assert_eq!(parse("--bench").unwrap().bench, Some(BenchOptions::default()));
+
+        let bench = parse("-b --runs 20 --warmup 0 --threshold 5 --baseline base.txt").unwrap().bench.unwrap();
+        assert_eq!(bench.runs, Some(20));
+        assert_eq!(bench.warmup, 0);
+        assert_eq!(bench.threshold, 5.0);
+        assert_eq!(bench.baseline, Some(PathBuf::from("base.txt")));
+
+        let bench = parse("--bench-time 0.5 --bench").unwrap().bench.unwrap();
+        assert_eq!(bench.time, Duration::from_millis(500));
+
+        assert!(parse("--runs 20").is_err());
+        assert!(parse("-b --runs 0").is_err());
+        assert!(parse("-b --bench-time -1").is_err());
+        assert!(parse("-b --warmup").is_err());
+        assert!(parse("-b --parallel").is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::days::grid::{Direction, Grid, GridIndex};
+
+/// Set of positions of a `width` x `num_rows` grid stored as one bit per cell,
+/// for the visited and seen sets of hot loops. Indexes like `Grid`, by position or `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridSet {
+    words: Vec<u64>,
+    width: usize,
+    num_rows: usize,
+}
+
+impl GridSet {
+    pub fn new(width: usize, num_rows: usize) -> GridSet {
+        GridSet { words: vec![0; (width * num_rows).div_ceil(64)], width, num_rows }
+    }
+
+    /// An empty set with the dimensions of `grid`.
+    pub fn for_grid<T: Copy>(grid: &Grid<T>) -> GridSet {
+        GridSet::new(grid.width(), grid.num_rows())
+    }
+
+    /// The positions of `grid` whose value matches `pred`.
+    pub fn from_grid<T: Copy>(grid: &Grid<T>, pred: impl Fn(T) -> bool) -> GridSet {
+        let mut set = GridSet::for_grid(grid);
+        for (pos, _) in grid.get_grid().iter().enumerate().filter(|&(_, &v)| pred(v)) {
+            set.insert(pos);
+        }
+        set
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    fn bit<I: GridIndex>(&self, index: I) -> (usize, u64) {
+        match index.to_pos(self.width, self.num_rows) {
+            Some(pos) => (pos / 64, 1 << (pos % 64)),
+            None => panic!("index out of bounds for a {}x{} grid set", self.width, self.num_rows),
+        }
+    }
+
+    /// Adds a position, returns true if it wasn't in the set yet.
+    pub fn insert<I: GridIndex>(&mut self, index: I) -> bool {
+        let (word, bit) = self.bit(index);
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    /// Removes a position, returns true if it was in the set.
+    pub fn remove<I: GridIndex>(&mut self, index: I) -> bool {
+        let (word, bit) = self.bit(index);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    /// Whether the position is in the set, positions outside the grid never are.
+    pub fn contains<I: GridIndex>(&self, index: I) -> bool {
+        index
+            .to_pos(self.width, self.num_rows)
+            .is_some_and(|pos| self.words[pos / 64] & (1 << (pos % 64)) != 0)
+    }
+
+    /// Empties the set while keeping its storage.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Number of positions in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    fn assert_same_size(&self, other: &GridSet) {
+        assert!(
+            (self.width, self.num_rows) == (other.width, other.num_rows),
+            "grid sets of {}x{} and {}x{} can't be combined",
+            self.width,
+            self.num_rows,
+            other.width,
+            other.num_rows
+        );
+    }
+
+    /// Adds every position of `other`, which must have the same dimensions.
+    pub fn union_with(&mut self, other: &GridSet) {
+        self.assert_same_size(other);
+        self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a |= b);
+    }
+
+    /// Keeps only the positions that are also in `other`, which must have the same dimensions.
+    pub fn intersect_with(&mut self, other: &GridSet) {
+        self.assert_same_size(other);
+        self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a &= b);
+    }
+
+    pub fn union(&self, other: &GridSet) -> GridSet {
+        let mut set = self.clone();
+        set.union_with(other);
+        set
+    }
+
+    pub fn intersection(&self, other: &GridSet) -> GridSet {
+        let mut set = self.clone();
+        set.intersect_with(other);
+        set
+    }
+
+    /// Positions in the set in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(idx, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    idx * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// Set of `(position, Direction)` states, e.g. to notice a walker that is back where it was
+/// facing the same way. One `GridSet` bit per position and direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionSet {
+    // bit `pos * 8 + direction` of a set that is eight times as wide as the grid
+    bits: GridSet,
+}
+
+impl DirectionSet {
+    pub fn new(width: usize, num_rows: usize) -> DirectionSet {
+        DirectionSet { bits: GridSet::new(width * Direction::ALL.len(), num_rows) }
+    }
+
+    pub fn for_grid<T: Copy>(grid: &Grid<T>) -> DirectionSet {
+        DirectionSet::new(grid.width(), grid.num_rows())
+    }
+
+    fn index((pos, direction): (usize, Direction)) -> usize {
+        pos * Direction::ALL.len() + direction as usize
+    }
+
+    /// Adds a state, returns true if it wasn't in the set yet.
+    pub fn insert(&mut self, state: (usize, Direction)) -> bool {
+        self.bits.insert(DirectionSet::index(state))
+    }
+
+    pub fn remove(&mut self, state: (usize, Direction)) -> bool {
+        self.bits.remove(DirectionSet::index(state))
+    }
+
+    pub fn contains(&self, state: (usize, Direction)) -> bool {
+        self.bits.contains(DirectionSet::index(state))
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// Number of states in the set.
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// States in the set, by position and then clockwise from `Up`.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        let directions = Direction::ALL.len();
+        self.bits.iter().map(move |idx| (idx / directions, Direction::ALL[idx % directions]))
+    }
+
+    /// The positions that are in the set with any direction.
+    pub fn positions(&self) -> GridSet {
+        let directions = Direction::ALL.len();
+        let mut set = GridSet::new(self.bits.width() / directions, self.bits.num_rows());
+        for idx in self.bits.iter() {
+            set.insert(idx / directions);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_set() {
+        let mut set = GridSet::new(10, 13);
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert((9, 12)));
+        assert!(set.insert(64));
+        assert!(set.contains((3, 0)));
+        assert!(!set.contains((10, 0)));
+        assert_eq!(set.count(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 64, 129]);
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert_eq!(set.count(), 2);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let grid = Grid::<char>::parse("#.#\n.##\n".as_bytes()).unwrap();
+        let walls = GridSet::from_grid(&grid, |c| c == '#');
+        let mut left = GridSet::for_grid(&grid);
+        left.insert((0, 0));
+        left.insert((0, 1));
+        assert_eq!(walls.union(&left).iter().collect::<Vec<_>>(), vec![0, 2, 3, 4, 5]);
+        assert_eq!(walls.intersection(&left).iter().collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds for a 3x2 grid set")]
+    fn test_out_of_bounds() {
+        GridSet::new(3, 2).insert((3, 0));
+    }
+
+    #[test]
+    fn test_direction_set() {
+        let mut states = DirectionSet::new(4, 4);
+        assert!(states.insert((5, Direction::Up)));
+        assert!(states.insert((5, Direction::Left)));
+        assert!(states.insert((15, Direction::UpLeft)));
+        assert!(!states.insert((5, Direction::Up)));
+        assert!(states.contains((5, Direction::Left)));
+        assert!(!states.contains((5, Direction::Right)));
+        assert_eq!(states.count(), 3);
+        assert_eq!(
+            states.iter().collect::<Vec<_>>(),
+            vec![(5, Direction::Up), (5, Direction::Left), (15, Direction::UpLeft)]
+        );
+        assert_eq!(states.positions().iter().collect::<Vec<_>>(), vec![5, 15]);
+    }
+}
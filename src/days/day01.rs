@@ -1,24 +1,29 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 
-fn parse_data<R: BufRead>(reader: R) -> (Vec<i32>, Vec<i32>) {
+fn parse_data<R: BufRead>(reader: R) -> Result<(Vec<i32>, Vec<i32>)> {
     let mut left = Vec::new();
     let mut right = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for line in error::lines(reader) {
+        let (line_no, line) = line?;
         let row = line
             .split_whitespace()
-            .map(|s| s.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>();
-        left.push(row[0]);
-        right.push(row[1]);
+            .map(|s| error::parse_at::<i32>(line_no, &line, s))
+            .collect::<Result<Vec<i32>>>()?;
+        let [l, r] = row[..] else {
+            return Err(Error::at(line_no, 1, format!("expected two numbers, found {}", row.len())));
+        };
+        left.push(l);
+        right.push(r);
     }
     left.sort();
     right.sort();
-    (left, right)
+    Ok((left, right))
 }
 
-fn part1(left: &Vec<i32>, right: &Vec<i32>) -> usize {
+fn part1(left: &[i32], right: &[i32]) -> usize {
     let total = left
         .iter()
         .zip(right.iter())
@@ -27,7 +32,7 @@ fn part1(left: &Vec<i32>, right: &Vec<i32>) -> usize {
     total as usize
 }
 
-fn part2(left: &Vec<i32>, right: &Vec<i32>) -> usize {
+fn part2(left: &[i32], right: &[i32]) -> usize {
     let total = left
         .iter()
         .map(|&l| right.iter().filter(|&r| *r == l).count() * (l as usize))
@@ -35,17 +40,31 @@ fn part2(left: &Vec<i32>, right: &Vec<i32>) -> usize {
     total
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/01.txt").expect("file not found"));
-    let (left, right) = parse_data(input_file);
-    let p1 = part1(&left, &right);
-    let p2 = part2(&left, &right);
-    (p1, p2)
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = (Vec<i32>, Vec<i32>);
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1((left, right): &Self::Input) -> Answer {
+        part1(left, right).into()
+    }
+
+    fn part2((left, right): &Self::Input) -> Answer {
+        part2(left, right).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::BufReader;
+    use crate::days::open_input;
 
     const TEST: &str = "\
 3   4
@@ -59,19 +78,27 @@ mod tests {
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let (left, right) = parse_data(input_file);
+        let (left, right) = parse_data(input_file).unwrap();
         assert_eq!(11, part1(&left, &right));
     }
 
     #[test]
     fn test_part2() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let (left, right) = parse_data(input_file);
+        let (left, right) = parse_data(input_file).unwrap();
         assert_eq!(31, part2(&left, &right));
     }
 
+    #[test]
+    fn test_parse_error() {
+        let err = parse_data(BufReader::new("3   4\n4   x\n".as_bytes())).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 5: invalid value `x`: invalid digit found in string");
+        let err = parse_data(BufReader::new("3   4\n4\n".as_bytes())).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected two numbers, found 1");
+    }
+
     #[test]
     fn test_day01() {
-        assert_eq!((1879048, 21024792), solve());
+        assert_eq!((1879048.into(), 21024792.into()), Day01::solve(&mut open_input(1)));
     }
 }
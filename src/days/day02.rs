@@ -1,17 +1,19 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Result};
+use crate::solution::Solution;
 
-fn parse_data<R: BufRead>(reader: R) -> Vec<Vec<i32>> {
+fn parse_data<R: BufRead>(reader: R) -> Result<Vec<Vec<i32>>> {
     let mut data = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for line in error::lines(reader) {
+        let (line_no, line) = line?;
         let row = line
             .split_whitespace()
-            .map(|s| s.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>();
+            .map(|s| error::parse_at::<i32>(line_no, &line, s))
+            .collect::<Result<Vec<i32>>>()?;
         data.push(row);
     }
-    data
+    Ok(data)
 }
 
 fn is_safe(report: &[i32]) -> bool {
@@ -19,7 +21,7 @@ fn is_safe(report: &[i32]) -> bool {
         && report.windows(2).all(|w| (w[0] - w[1]).abs() <= 3)
 }
 
-fn part1(data: &Vec<Vec<i32>>) -> usize {
+fn part1(data: &[Vec<i32>]) -> usize {
     let result = data.iter().map(|row| is_safe(row)).filter(|&b| b).count();
     result
 }
@@ -28,7 +30,7 @@ fn and(a: &[bool], b: &[bool]) -> Vec<bool> {
     a.iter().zip(b).map(|(x, y)| x & y).collect()
 }
 
-fn part2(data: &Vec<Vec<i32>>) -> usize {
+fn part2(data: &[Vec<i32>]) -> usize {
     fn is_safe_damp(report: &[i32]) -> bool {
         let increasing = report
             .windows(2)
@@ -74,17 +76,32 @@ fn part2(data: &Vec<Vec<i32>>) -> usize {
         .count();
     result
 }
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/02.txt").unwrap());
-    let data = parse_data(input_file);
-    let p1 = part1(&data);
-    let p2 = part2(&data);
-    (p1, p2)
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::BufReader;
+    use crate::days::open_input;
 
     const TEST: &str = "\
 7 6 4 2 1
@@ -98,19 +115,19 @@ mod tests {
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let data = parse_data(input_file);
+        let data = parse_data(input_file).unwrap();
         assert_eq!(2, part1(&data));
     }
 
     #[test]
     fn test_part2() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let data = parse_data(input_file);
+        let data = parse_data(input_file).unwrap();
         assert_eq!(4, part2(&data));
     }
 
     #[test]
     fn test_solution() {
-        assert_eq!((402, 455), solve());
+        assert_eq!((402.into(), 455.into()), Day02::solve(&mut open_input(2)));
     }
 }
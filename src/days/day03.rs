@@ -1,56 +1,103 @@
 use regex::Regex;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Result};
+use crate::solution::Solution;
 
-fn combined<R: BufRead>(reader: R) -> (usize, usize) {
-    let re = Regex::new(r"mul\((\d+),(\d+)\)|(do\(\))|(don't\(\))").unwrap();
-    let mut p2 = 0;
-    let mut p1 = 0;
-    let mut state_do = true;
-    for line in reader.lines() {
-        let line = line.unwrap();
+pub enum Instruction {
+    Do,
+    Dont,
+    Mul(usize, usize),
+}
+
+fn parse_data<R: BufRead>(reader: R) -> Result<Vec<Instruction>> {
+    let re = Regex::new(r"mul\((\d+),(\d+)\)|(do\(\))|(don't\(\))").expect("valid regex");
+    let mut instructions = vec![];
+    for line in error::lines(reader) {
+        let (line_no, line) = line?;
         for ins in re.captures_iter(&line) {
-            match ins.get(0).unwrap().as_str() {
-                "do()" => state_do = true,
-                "don't()" => state_do = false,
+            instructions.push(match &ins[0] {
+                "do()" => Instruction::Do,
+                "don't()" => Instruction::Dont,
                 _ => {
-                    let n1 = ins.get(1).unwrap().as_str().parse::<usize>().unwrap();
-                    let n2 = ins.get(2).unwrap().as_str().parse::<usize>().unwrap();
-                    if state_do {
-                        p2 += n1 * n2;
-                    }
-                    p1 += n1 * n2;
+                    let n1 = error::parse_at::<usize>(line_no, &line, ins.get(1).unwrap().as_str())?;
+                    let n2 = error::parse_at::<usize>(line_no, &line, ins.get(2).unwrap().as_str())?;
+                    Instruction::Mul(n1, n2)
+                }
+            });
+        }
+    }
+    Ok(instructions)
+}
+
+fn part1(instructions: &[Instruction]) -> usize {
+    instructions
+        .iter()
+        .map(|ins| match ins {
+            Instruction::Mul(n1, n2) => n1 * n2,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn part2(instructions: &[Instruction]) -> usize {
+    let mut state_do = true;
+    let mut p2 = 0;
+    for ins in instructions {
+        match ins {
+            Instruction::Do => state_do = true,
+            Instruction::Dont => state_do = false,
+            Instruction::Mul(n1, n2) => {
+                if state_do {
+                    p2 += n1 * n2;
                 }
             }
         }
     }
-    (p1, p2)
+    p2
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/03.txt").unwrap());
-    combined(input_file)
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Instruction>;
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::BufReader;
+    use crate::days::open_input;
 
     const TEST: &str = "\
 xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
     #[test]
     fn test_part1() {
-        assert_eq!(161, combined(BufReader::new(TEST.as_bytes())).0);
+        assert_eq!(161, part1(&parse_data(BufReader::new(TEST.as_bytes())).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(48, combined(BufReader::new(TEST.as_bytes())).1);
+        assert_eq!(48, part2(&parse_data(BufReader::new(TEST.as_bytes())).unwrap()));
     }
 
     #[test]
     fn test_solve() {
-        assert_eq!((169021493, 111762583), solve());
+        assert_eq!((169021493.into(), 111762583.into()), Day03::solve(&mut open_input(3)));
     }
 }
@@ -1,23 +1,21 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-use crate::days::grid::{Grid, ParseData};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::days::grid::{Direction, Grid};
+use crate::days::pattern::Pattern;
+use crate::days::transform::Transform;
 
-fn find_words(grid: &Grid<char>, target_str: &[char], directions: &[(isize, isize)]) -> Vec<Vec<usize>> {
+fn find_words(grid: &Grid<char>, target_str: &[char], directions: &[Direction]) -> Vec<Vec<usize>> {
     let mut solutions = vec![];
     for pos in 0..grid.len() {
-        for direction in directions {
-            let mut current_pos = pos;
-            let mut sol = vec![];
-            while sol.len() < target_str.len()
-                && grid.peek(current_pos) == target_str[sol.len()]
-            {
-                sol.push(current_pos);
-                match grid.move_pos(current_pos, *direction) {
-                    Some(new_pos) => current_pos = new_pos,
-                    None => break,
-                }
-            }
+        for &direction in directions {
+            let sol = grid
+                .line_from(pos, direction)
+                .zip(target_str)
+                .take_while(|((_, c), target)| c == *target)
+                .map(|((p, _), _)| p)
+                .collect::<Vec<_>>();
             if sol.len() == target_str.len() {
                 solutions.push(sol);
             }
@@ -28,47 +26,40 @@ fn find_words(grid: &Grid<char>, target_str: &[char], directions: &[(isize, isiz
 
 fn part1(grid: &Grid<char>) -> usize {
     let target_str = ['X', 'M', 'A', 'S'];
-    let directions = [
-        (-1, 0),  // left
-        (1, 0),   // right
-        (0, 1),   // up
-        (0, -1),  // down
-        (-1, 1),  // upper left
-        (1, 1),   // upper right
-        (-1, -1), // lower left
-        (1, -1),  // lower right
-    ];
-    find_words(grid, &target_str, &directions).iter().count()
+    find_words(grid, &target_str, &Direction::ALL).len()
 }
 
 fn part2(grid: &Grid<char>) -> usize {
-    let target_str = ['M', 'A', 'S'];
-    let directions = [
-        (-1, 1),  // upper left
-        (1, 1),   // upper right
-        (-1, -1), // lower left
-        (1, -1),  // lower right
-    ];
-    let solutions = find_words(grid, &target_str, &directions);
-    let mut result = HashMap::new();
-    // looking for solutions that share the a position
-    for sol in solutions {
-        *result.entry(sol[1]).or_insert(0) += 1;
-    }
-    result.iter().filter(|&(_, &v)| v > 1).count()
+    // two MAS crossing on their A, in any of the four ways the cross can be turned
+    let cross = Pattern::parse("M.S\n.A.\nM.S\n", '.').unwrap();
+    grid.find_pattern_transformed(&cross, &Transform::ROTATIONS).len()
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/04.txt").unwrap());
-    let grid: Grid<char> = Grid::parse_data(input_file);
-    let p1 = part1(&grid);
-    let p2 = part2(&grid);
-    (p1, p2)
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Ceres Search";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Answer {
+        part1(grid).into()
+    }
+
+    fn part2(grid: &Self::Input) -> Answer {
+        part2(grid).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::BufReader;
+    use crate::days::open_input;
 
     const TEST: &str = "\
 MMMSXXMASM
@@ -86,19 +77,19 @@ MXMXAXMASX
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let grid = Grid::parse_data(input_file);
+        let grid = Grid::parse(input_file).unwrap();
         assert_eq!(18, part1(&grid));
     }
 
     #[test]
     fn test_part2() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let grid = Grid::parse_data(input_file);
+        let grid = Grid::parse(input_file).unwrap();
         assert_eq!(9, part2(&grid));
     }
 
     #[test]
     fn test_sol() {
-        assert_eq!((2378, 1796), solve());
+        assert_eq!((2378.into(), 1796.into()), Day04::solve(&mut open_input(4)));
     }
 }
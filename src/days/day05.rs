@@ -1,84 +1,104 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 
-fn parse_data<R: BufRead>(reader: R) -> (HashMap<usize, HashSet<usize>>, Vec<Vec<usize>>) {
+type Rules = HashMap<usize, HashSet<usize>>;
+
+fn parse_data<R: BufRead>(reader: R) -> Result<(Rules, Vec<Vec<usize>>)> {
     let mut ordering_rules = HashMap::new();
     let mut pages = Vec::new();
     let mut parse_rules = true;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    for line in error::lines(reader) {
+        let (line_no, line) = line?;
         if parse_rules {
-            if line == "" {
+            if line.is_empty() {
                 parse_rules = false;
             } else {
-                let rule = line
-                    .split('|')
-                    .map(|s| s.parse::<usize>().unwrap())
-                    .collect::<Vec<usize>>();
+                let Some((before, after)) = line.split_once('|') else {
+                    return Err(Error::at(line_no, 1, "expected a rule `X|Y`"));
+                };
                 ordering_rules
-                    .entry(rule[0])
+                    .entry(error::parse_at::<usize>(line_no, &line, before)?)
                     .or_insert(HashSet::new())
-                    .insert(rule[1]);
+                    .insert(error::parse_at::<usize>(line_no, &line, after)?);
             }
         } else {
             let row = line
                 .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect::<Vec<usize>>();
+                .map(|s| error::parse_at::<usize>(line_no, &line, s))
+                .collect::<Result<Vec<usize>>>()?;
             pages.push(row);
         }
     }
-    (ordering_rules, pages)
+    Ok((ordering_rules, pages))
 }
 
-fn combined(
-    ordering_rules: &HashMap<usize, HashSet<usize>>,
-    updates: &Vec<Vec<usize>>,
-) -> (usize, usize) {
-    fn is_valid(ordering_rules: &HashMap<usize, HashSet<usize>>, update: &[usize]) -> bool {
-        let mut previous = HashSet::with_capacity(update.len());
-        for page in update.iter() {
-            if let Some(rules) = ordering_rules.get(page) {
-                let res = rules.intersection(&previous).count();
-                if res > 0 {
-                    return false;
-                }
+fn is_valid(ordering_rules: &Rules, update: &[usize]) -> bool {
+    let mut previous = HashSet::with_capacity(update.len());
+    for page in update.iter() {
+        if let Some(rules) = ordering_rules.get(page) {
+            let res = rules.intersection(&previous).count();
+            if res > 0 {
+                return false;
             }
-            previous.insert(page.to_owned());
         }
-        true
+        previous.insert(page.to_owned());
     }
-    let mut p1 = 0;
+    true
+}
+
+fn part1(ordering_rules: &Rules, updates: &[Vec<usize>]) -> usize {
+    updates
+        .iter()
+        .filter(|update| is_valid(ordering_rules, update))
+        .map(|update| update[update.len() / 2])
+        .sum()
+}
+
+fn part2(ordering_rules: &Rules, updates: &[Vec<usize>]) -> usize {
     let mut p2 = 0;
-    for update in updates.iter() {
-        if !is_valid(ordering_rules, update) {
-            let mut fixed = Vec::with_capacity(update.len());
-            while fixed.len() != update.len() {
-                let mut addition_idx = fixed.len();
-                fixed.push(update[fixed.len()]);
-                while !is_valid(ordering_rules, &fixed[..addition_idx + 1]) {
-                    fixed.swap(addition_idx, addition_idx - 1);
-                    addition_idx -= 1;
-                }
+    for update in updates.iter().filter(|update| !is_valid(ordering_rules, update)) {
+        let mut fixed = Vec::with_capacity(update.len());
+        while fixed.len() != update.len() {
+            let mut addition_idx = fixed.len();
+            fixed.push(update[fixed.len()]);
+            while !is_valid(ordering_rules, &fixed[..addition_idx + 1]) {
+                fixed.swap(addition_idx, addition_idx - 1);
+                addition_idx -= 1;
             }
-            p2 += fixed[fixed.len() / 2];
-        } else {
-            p1 += update[update.len() / 2];
         }
+        p2 += fixed[fixed.len() / 2];
     }
-    (p1, p2)
+    p2
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/05.txt").unwrap());
-    let (ordering_rules, updates) = parse_data(input_file);
-    combined(&ordering_rules, &updates)
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = (Rules, Vec<Vec<usize>>);
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1((ordering_rules, updates): &Self::Input) -> Answer {
+        part1(ordering_rules, updates).into()
+    }
+
+    fn part2((ordering_rules, updates): &Self::Input) -> Answer {
+        part2(ordering_rules, updates).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::BufReader;
+    use crate::days::open_input;
 
     const TEST: &str = "\
 47|53
@@ -114,19 +134,19 @@ mod tests {
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let (ordering_rules, updates) = parse_data(input_file);
-        assert_eq!(143, combined(&ordering_rules, &updates).0);
+        let (ordering_rules, updates) = parse_data(input_file).unwrap();
+        assert_eq!(143, part1(&ordering_rules, &updates));
     }
 
     #[test]
     fn test_part2() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let (ordering_rules, updates) = parse_data(input_file);
-        assert_eq!(123, combined(&ordering_rules, &updates).1);
+        let (ordering_rules, updates) = parse_data(input_file).unwrap();
+        assert_eq!(123, part2(&ordering_rules, &updates));
     }
 
     #[test]
     fn test_sol() {
-        assert_eq!((6260, 5346), solve());
+        assert_eq!((6260.into(), 5346.into()), Day05::solve(&mut open_input(5)));
     }
 }
@@ -1,19 +1,26 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 
-fn parse_data<R: BufRead>(reader: R) -> Vec<(usize, Vec<usize>)> {
+fn parse_data<R: BufRead>(reader: R) -> Result<Vec<(usize, Vec<usize>)>> {
     let mut data = vec![];
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let (target_str, rest) = line.split_once(": ").unwrap();
-        let target = target_str.parse::<usize>().unwrap();
+    for line in error::lines(reader) {
+        let (line_no, line) = line?;
+        let Some((target_str, rest)) = line.split_once(": ") else {
+            return Err(Error::at(line_no, 1, "expected `<test value>: <numbers>`"));
+        };
+        let target = error::parse_at::<usize>(line_no, &line, target_str)?;
         let vals = rest
             .split_whitespace()
-            .map(|s| s.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+            .map(|s| error::parse_at::<usize>(line_no, &line, s))
+            .collect::<Result<Vec<usize>>>()?;
+        if vals.is_empty() {
+            return Err(Error::at(line_no, error::column_of(&line, rest), "expected at least one number"));
+        }
         data.push((target, vals));
     }
-    data
+    Ok(data)
 }
 
 fn concat(a: usize, b: usize) -> usize {
@@ -26,50 +33,69 @@ fn concat(a: usize, b: usize) -> usize {
     a * 10usize.pow(get_num_digits(b, 0) as u32) + b
 }
 
-fn combined(data: &Vec<(usize, Vec<usize>)>) -> (usize, usize) {
-    fn aux_p1(target: usize, vals: &[usize], acc: usize) -> bool {
-        if vals.is_empty() {
-            return acc == target;
-        }
-        if acc > target {
-            return false;
-        }
-        aux_p1(target, &vals[1..], acc + vals[0]) || aux_p1(target, &vals[1..], acc * vals[0])
+fn aux_p1(target: usize, vals: &[usize], acc: usize) -> bool {
+    if vals.is_empty() {
+        return acc == target;
+    }
+    if acc > target {
+        return false;
     }
+    aux_p1(target, &vals[1..], acc + vals[0]) || aux_p1(target, &vals[1..], acc * vals[0])
+}
 
-    fn aux_p2(target: usize, vals: &[usize], acc: usize) -> bool {
-        if vals.is_empty() {
-            return acc == target;
-        }
-        if acc > target {
-            return false;
-        }
-        aux_p2(target, &vals[1..], acc + vals[0])
-            || aux_p2(target, &vals[1..], acc * vals[0])
-            || aux_p2(target, &vals[1..], concat(acc, vals[0]))
+fn aux_p2(target: usize, vals: &[usize], acc: usize) -> bool {
+    if vals.is_empty() {
+        return acc == target;
     }
-    let mut p1 = 0;
-    let mut p2 = 0;
-    for (target, vals) in data {
-        if aux_p1(*target, &vals[1..], vals[0]) {
-            p1 += target;
-            p2 += target;
-        } else if aux_p2(*target, &vals[1..], vals[0]) {
-            p2 += target;
-        }
+    if acc > target {
+        return false;
     }
-    (p1, p2)
+    aux_p2(target, &vals[1..], acc + vals[0])
+        || aux_p2(target, &vals[1..], acc * vals[0])
+        || aux_p2(target, &vals[1..], concat(acc, vals[0]))
+}
+
+fn part1(data: &[(usize, Vec<usize>)]) -> usize {
+    data.iter()
+        .filter(|(target, vals)| aux_p1(*target, &vals[1..], vals[0]))
+        .map(|(target, _)| target)
+        .sum()
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/07.txt").expect("file not found"));
-    let data = parse_data(input_file);
-    combined(&data)
+fn part2(data: &[(usize, Vec<usize>)]) -> usize {
+    // the cheaper p1 check settles most equations before trying concatenation
+    data.iter()
+        .filter(|(target, vals)| {
+            aux_p1(*target, &vals[1..], vals[0]) || aux_p2(*target, &vals[1..], vals[0])
+        })
+        .map(|(target, _)| target)
+        .sum()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<(usize, Vec<usize>)>;
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::open_input;
     use std::io::BufReader;
 
     const TEST: &str = "\
@@ -87,8 +113,14 @@ mod tests {
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let data = parse_data(input_file);
-        assert_eq!(combined(&data).0, 3749);
+        let data = parse_data(input_file).unwrap();
+        assert_eq!(part1(&data), 3749);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_data(BufReader::new("190: 10 19\n5: \n".as_bytes())).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 4: expected at least one number");
     }
 
     #[test]
@@ -100,12 +132,12 @@ mod tests {
     #[test]
     fn test_part2() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let data = parse_data(input_file);
-        assert_eq!(combined(&data).1, 11387);
+        let data = parse_data(input_file).unwrap();
+        assert_eq!(part2(&data), 11387);
     }
 
     #[test]
     fn test_solve() {
-        assert_eq!((2501605301465, 44841372855953), solve());
+        assert_eq!((Answer::from(2501605301465u64), Answer::from(44841372855953u64)), Day07::solve(&mut open_input(7)));
     }
 }
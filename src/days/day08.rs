@@ -1,21 +1,27 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-struct AntennaMap {
+use std::collections::HashMap;
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
+use crate::days::bitset::GridSet;
+
+pub struct AntennaMap {
     array_width: isize,
     num_rows: isize,
     antenna: HashMap<char, Vec<usize>>,
 }
 
 impl AntennaMap {
-    fn parse_data<R: BufRead>(reader: R) -> AntennaMap {
-        let mut file_iter = reader.lines().peekable();
+    fn parse_data<R: BufRead>(reader: R) -> Result<AntennaMap> {
         let mut antenna = HashMap::new();
-        let array_width = file_iter.peek().unwrap().as_ref().unwrap().len() as isize;
+        let mut array_width = 0;
         let mut row = 0;
-        while let Some(line) = file_iter.next() {
-            line.unwrap().chars().enumerate().for_each(|(idx, c)| {
+        for line in error::lines(reader) {
+            let (line_no, line) = line?;
+            if line_no == 1 {
+                array_width = line.len() as isize;
+            }
+            line.chars().enumerate().for_each(|(idx, c)| {
                 if c != '.' {
                     antenna
                         .entry(c)
@@ -25,11 +31,14 @@ impl AntennaMap {
             });
             row += 1;
         }
-        AntennaMap {
+        if array_width == 0 {
+            return Err(Error::invalid("empty map"));
+        }
+        Ok(AntennaMap {
             array_width,
             num_rows: row,
             antenna,
-        }
+        })
     }
 
     fn pos_to_coords(&self, pos: usize) -> (isize, isize) {
@@ -41,6 +50,10 @@ impl AntennaMap {
         y * self.array_width as usize + x
     }
 
+    fn antinodes(&self) -> GridSet {
+        GridSet::new(self.array_width as usize, self.num_rows as usize)
+    }
+
     fn is_valid_coords(&self, x: isize, y: isize) -> bool {
         (x >= 0) && (x < self.array_width) && (y >= 0) && (y < self.num_rows)
     }
@@ -57,54 +70,79 @@ pub fn gcd(mut n: usize, mut m: usize) -> usize {
     n
 }
 
-fn combined(grid: &AntennaMap) -> (usize, usize) {
-    let mut solutions = HashSet::new();
-    let mut p1_solutions = HashSet::new();
-    for (_, positions) in &grid.antenna {
-        for (idx, &a) in positions.iter().enumerate() {
-            for &b in positions[idx + 1..].iter() {
-                let (x1, y1) = grid.pos_to_coords(a);
-                let (x2, y2) = grid.pos_to_coords(b);
-
-                let dx = x2 - x1;
-                let dy = y2 - y1;
-                let p1 = (x1 - dx, y1 - dy);
-                let p2 = (x2 + dx, y2 + dy);
-                for p in [p1, p2].iter() {
-                    if grid.is_valid_coords(p.0, p.1) {
-                        let antipod = grid.coords_to_pos(p.0 as usize, p.1 as usize);
-                        p1_solutions.insert(antipod);
-                    }
-                }
+fn antenna_pairs(grid: &AntennaMap) -> impl Iterator<Item = ((isize, isize), (isize, isize))> + '_ {
+    grid.antenna.values().flat_map(move |positions| {
+        positions.iter().enumerate().flat_map(move |(idx, &a)| {
+            positions[idx + 1..]
+                .iter()
+                .map(move |&b| (grid.pos_to_coords(a), grid.pos_to_coords(b)))
+        })
+    })
+}
 
-                // normalize delta for p2
-                let g = gcd(dx.abs() as usize, dy.abs() as usize);
-                let dx = dx / g as isize;
-                let dy = dy / g as isize;
-
-                for delta in [(dx, dy), (-dx, -dy)].iter() {
-                    let mut cur_pos = (x1, y1);
-                    while grid.is_valid_coords(cur_pos.0, cur_pos.1) {
-                        let antipod = grid.coords_to_pos(cur_pos.0 as usize, cur_pos.1 as usize);
-                        solutions.insert(antipod);
-                        cur_pos = (cur_pos.0 + delta.0, cur_pos.1 + delta.1);
-                    }
-                }
+fn part1(grid: &AntennaMap) -> usize {
+    let mut solutions = grid.antinodes();
+    for ((x1, y1), (x2, y2)) in antenna_pairs(grid) {
+        let dx = x2 - x1;
+        let dy = y2 - y1;
+        let p1 = (x1 - dx, y1 - dy);
+        let p2 = (x2 + dx, y2 + dy);
+        for p in [p1, p2].iter() {
+            if grid.is_valid_coords(p.0, p.1) {
+                let antipod = grid.coords_to_pos(p.0 as usize, p.1 as usize);
+                solutions.insert(antipod);
             }
         }
     }
-    (p1_solutions.len(), solutions.len())
+    solutions.count()
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/08.txt").expect("file not found"));
-    let grid = AntennaMap::parse_data(input_file);
-    combined(&grid)
+fn part2(grid: &AntennaMap) -> usize {
+    let mut solutions = grid.antinodes();
+    for ((x1, y1), (x2, y2)) in antenna_pairs(grid) {
+        // normalize delta so every grid point on the line is hit
+        let dx = x2 - x1;
+        let dy = y2 - y1;
+        let g = gcd(dx.unsigned_abs(), dy.unsigned_abs());
+        let dx = dx / g as isize;
+        let dy = dy / g as isize;
+
+        for delta in [(dx, dy), (-dx, -dy)].iter() {
+            let mut cur_pos = (x1, y1);
+            while grid.is_valid_coords(cur_pos.0, cur_pos.1) {
+                let antipod = grid.coords_to_pos(cur_pos.0 as usize, cur_pos.1 as usize);
+                solutions.insert(antipod);
+                cur_pos = (cur_pos.0 + delta.0, cur_pos.1 + delta.1);
+            }
+        }
+    }
+    solutions.count()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = AntennaMap;
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        AntennaMap::parse_data(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Answer {
+        part1(grid).into()
+    }
+
+    fn part2(grid: &Self::Input) -> Answer {
+        part2(grid).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::open_input;
     use std::io::BufReader;
 
     const TEST: &str = "\
@@ -125,19 +163,19 @@ mod tests {
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let grid = AntennaMap::parse_data(input_file);
-        assert_eq!(combined(&grid).0, 14);
+        let grid = AntennaMap::parse_data(input_file).unwrap();
+        assert_eq!(part1(&grid), 14);
     }
 
     #[test]
     fn test_part2() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let grid = AntennaMap::parse_data(input_file);
-        assert_eq!(combined(&grid).1, 34);
+        let grid = AntennaMap::parse_data(input_file).unwrap();
+        assert_eq!(part2(&grid), 34);
     }
 
     #[test]
     fn test_sol() {
-        assert_eq!(solve(), (273, 1017));
+        assert_eq!(Day08::solve(&mut open_input(8)), (273.into(), 1017.into()));
     }
 }
@@ -1,29 +1,35 @@
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 use std::vec;
 
-fn parse_data<R: BufRead>(reader: R) -> VecDeque<(u64, u64, u64)> {
-    let line = reader.lines().next().unwrap().unwrap().chars().collect::<Vec<char>>();
+fn parse_data<R: BufRead>(reader: R) -> Result<VecDeque<(u64, u64, u64)>> {
+    let Some(line) = error::lines(reader).next() else {
+        return Err(Error::invalid("empty disk map"));
+    };
+    let (line_no, line) = line?;
+    let line = line.chars().collect::<Vec<char>>();
     let mut data = VecDeque::new();
     let mut idx = 0;
     while idx < line.len() {
-        let file_blocks = line[idx].to_digit(10).unwrap() as u64;
+        let file_blocks = error::digit_at(line_no, idx, line[idx])? as u64;
         let free_space = if idx + 1 < line.len() {
-            line[idx + 1].to_digit(10).unwrap() as u64
+            error::digit_at(line_no, idx + 1, line[idx + 1])? as u64
         } else {
             0
         };
         data.push_back(((idx / 2) as u64, file_blocks, free_space));
         idx += 2;
     }
-    data
+    Ok(data)
 }
 
 fn part1(mut data: VecDeque<(u64, u64, u64)>) -> usize {
     let mut sol = Vec::new();
     let mut front_free = 0;
-    while data.len() > 0 {
+    while !data.is_empty() {
         if front_free == 0 { // place the front blocks
             let (front_id, front_blocks, new_free) = data.pop_front().unwrap();
             sol.extend(vec![front_id; front_blocks as usize]);
@@ -42,7 +48,6 @@ fn part1(mut data: VecDeque<(u64, u64, u64)>) -> usize {
     sol.iter().enumerate().map(|(idx, &val)| idx * val as usize).sum::<usize>()
 }
 
-// p2 requires u64 should update the template to use u64
 fn part2(mut data: VecDeque<(u64, u64, u64)>) -> u64 {
     let mut attempted = VecDeque::new();
     let mut processed = vec![false; data.len()];
@@ -96,36 +101,50 @@ fn part2(mut data: VecDeque<(u64, u64, u64)>) -> u64 {
     p2
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/09.txt").expect("file not found"));
-    let data = parse_data(input_file);
-    let p1 = part1(data.clone());
-    let p2 = part2(data);
-    (p1, p2 as usize)
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = VecDeque<(u64, u64, u64)>;
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input.clone()).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input.clone()).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::BufReader;
+    use crate::days::open_input;
 
     const TEST: &str = "2333133121414131402";
 
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let data = parse_data(input_file);
+        let data = parse_data(input_file).unwrap();
         assert_eq!(part1(data), 1928);
     }
 
     #[test]
     fn test_part2() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let data = parse_data(input_file);
+        let data = parse_data(input_file).unwrap();
         assert_eq!(2858, part2(data));
     }
 
     #[test]
     fn test_solve() {
-        assert_eq!((6386640365805, 6423258376982), solve());
+        assert_eq!((Answer::from(6386640365805u64), Answer::from(6423258376982u64)), Day09::solve(&mut open_input(9)));
     }
 }
@@ -1,40 +1,55 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::BufReader;
-
-use crate::days::grid::{Grid, ParseData};
-
-fn combined(grid: &Grid<u32>) -> (usize, usize) {
-    let mut trail_index: HashMap<usize, HashSet<Vec<usize>>> = HashMap::new();
-
-    fn find_trails(grid: &Grid<u32>, cur_pos: usize, mut path: Vec<usize>, all_paths: &mut HashMap<usize, HashSet<Vec<usize>>>) {
-        if path.len() == 10 {
-            all_paths.entry(path[0]).or_insert(HashSet::new()).insert(path[1..].to_owned());
-        } else if grid.peek(cur_pos) == path.len() as u32 {
-            path.push(cur_pos);
-            [(0, -1), (0, 1), (-1, 0), (1, 0)].iter().filter_map(|&dir| grid.move_pos(cur_pos, dir)).for_each(|x| find_trails(grid, x, path.clone(), all_paths));
-        }
-    }
+use std::io::BufRead;
 
-    for start in 0..grid.len() {
-        find_trails(grid, start, Vec::with_capacity(10), &mut trail_index);
-    }
-    // get number of unique trails
-    let p2 = trail_index.values().map(|x| x.len()).sum();
+use crate::days::grid::Grid;
+use crate::days::pathfinding::Search;
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::solution::Solution;
+
+/// Searches from every trailhead, a trail climbs exactly one height per step.
+fn find_all_trails(grid: &Grid<u32>) -> impl Iterator<Item = Search<usize>> + '_ {
+    (0..grid.len())
+        .filter(|&pos| grid[pos] == 0)
+        .map(|start| grid.bfs(start, |from, to| to == from + 1))
+}
+
+fn part1(grid: &Grid<u32>) -> usize {
     // for p1 we want distinct start and end not paths
-    let p1 = trail_index.values().map(|paths| paths.iter().map(|x| *x.last().unwrap()).collect::<HashSet<usize>>().len()).sum();
-    (p1, p2)
+    find_all_trails(grid).map(|trails| trails.reached().filter(|&(pos, _)| grid[pos] == 9).count()).sum()
+}
+
+fn part2(grid: &Grid<u32>) -> u64 {
+    // every trail to a 9 is 9 steps long, so all trails are shortest paths
+    find_all_trails(grid)
+        .map(|trails| trails.path_counts().into_iter().filter(|&(pos, _)| grid[pos] == 9).map(|(_, count)| count).sum::<u64>())
+        .sum()
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/10.txt").unwrap());
-    let grid = Grid::parse_data(input_file);
-    combined(&grid)
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Grid<u32>;
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Hoof It";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Answer {
+        part1(grid).into()
+    }
+
+    fn part2(grid: &Self::Input) -> Answer {
+        part2(grid).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::BufReader;
+    use crate::days::open_input;
 
     const TEST: &str = "\
 89010123
@@ -50,12 +65,12 @@ mod tests {
     #[test]
     fn test_combined() {
         let input_file = BufReader::new(TEST.as_bytes());
-        let grid = Grid::parse_data(input_file);
-        assert_eq!(combined(&grid), (36, 81));
+        let grid = Grid::parse(input_file).unwrap();
+        assert_eq!((part1(&grid), part2(&grid)), (36, 81));
     }
 
     #[test]
     fn test_sol() {
-        assert_eq!((822, 1801), solve());
+        assert_eq!((822.into(), 1801.into()), Day10::solve(&mut open_input(10)));
     }
 }
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 use tailcall::tailcall;
 
+#[allow(unreachable_code)]
 #[tailcall]
 fn get_num_digits(x: usize, acc: usize) -> usize {
     if x == 0 {
@@ -22,7 +25,7 @@ fn change_stone(stone: u64, cache: &mut HashMap<(u64, u64), u64>, changes: u64)
         change_stone(1, cache, changes - 1)
     } else {
         let num_digits = get_num_digits(stone as usize, 0);
-        if num_digits % 2 == 0 {
+        if num_digits.is_multiple_of(2) {
             let left = stone / 10u64.pow((num_digits / 2) as u32);
             let right = stone % 10u64.pow((num_digits / 2) as u32);
             change_stone(right, cache, changes - 1) + change_stone(left, cache, changes - 1)
@@ -34,35 +37,55 @@ fn change_stone(stone: u64, cache: &mut HashMap<(u64, u64), u64>, changes: u64)
     ans
 }
 
-fn combined<R: BufRead>(reader: R) -> (u64, u64) {
-    let data = reader.lines().next().unwrap().unwrap().split_whitespace().map(|s| s.parse::<u64>().unwrap()).collect::<Vec<u64>>();
+fn parse_data<R: BufRead>(reader: R) -> Result<Vec<u64>> {
+    let Some(line) = error::lines(reader).next() else {
+        return Err(Error::invalid("no stones"));
+    };
+    let (line_no, line) = line?;
+    line.split_whitespace().map(|s| error::parse_at::<u64>(line_no, &line, s)).collect::<Result<Vec<u64>>>()
+}
+
+fn blink(data: &[u64], changes: u64) -> u64 {
     let mut cache = HashMap::new();
-    let p1: u64 = data.iter().map(|&stone| change_stone(stone, &mut cache, 25)).sum();
-    let p2: u64 = data.iter().map(|&stone| change_stone(stone, &mut cache, 75)).sum();
-    (p1, p2)
+    data.iter().map(|&stone| change_stone(stone, &mut cache, changes)).sum()
 }
 
-pub fn solve() -> (usize, usize) {
-    let input_file = BufReader::new(File::open("input/11.txt").unwrap());
-    let (p1, p2) = combined(input_file);
-    (p1 as usize, p2 as usize)
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<u64>;
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        blink(input, 25).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        blink(input, 75).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::io::BufReader;
     use super::*;
+    use crate::days::open_input;
 
     const TEST: &str = "125 17";
 
     #[test]
     fn test_part1() {
         let input_file = BufReader::new(TEST.as_bytes());
-        assert_eq!(combined(input_file).0, 55312);
+        assert_eq!(blink(&parse_data(input_file).unwrap(), 25), 55312);
     }
     
     #[test]
     fn test_solve() {
-        assert_eq!((189167, 225253278506288), solve());
+        assert_eq!((189167.into(), Answer::from(225253278506288u64)), Day11::solve(&mut open_input(11)));
     }
 }
\ No newline at end of file
@@ -1,26 +1,303 @@
 use std::fmt;
 use std::fmt::Display;
 use std::io::BufRead;
+use std::ops::{Index, IndexMut};
 
-// todo make direction enum and support stepping in each direction
+use crate::error::{Error, Result};
+
+/// One of the eight directions on a grid, rows grow downwards so `Up` is `(0, -1)`.
+/// Puzzles that only move orthogonally use `Direction::CARDINAL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// All directions, clockwise starting at `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// The four orthogonal directions, clockwise starting at `Up`.
+    pub const CARDINAL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// The four diagonal directions, clockwise starting at `UpRight`.
+    pub const DIAGONAL: [Direction; 4] = [Direction::UpRight, Direction::DownRight, Direction::DownLeft, Direction::UpLeft];
+
+    /// `(dx, dy)` of a single step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    /// Turns clockwise by `eighths` of a full turn.
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Turns 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right_45(self) -> Direction {
+        self.rotate(1)
+    }
+
+    /// Turns 45 degrees counterclockwise.
+    pub fn turn_left_45(self) -> Direction {
+        self.rotate(7)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Direction of an arrow character, `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+/// Distance used for neighborhoods larger than the adjacent cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// `|dx| + |dy|`, a diamond around the position.
+    Manhattan,
+    /// `max(|dx|, |dy|)`, a square around the position.
+    Chebyshev,
+}
+
+impl Metric {
+    pub fn distance(self, dx: isize, dy: isize) -> usize {
+        match self {
+            Metric::Manhattan => dx.unsigned_abs() + dy.unsigned_abs(),
+            Metric::Chebyshev => dx.unsigned_abs().max(dy.unsigned_abs()),
+        }
+    }
+
+    /// Offsets whose distance lies in `min..=max`, row by row.
+    fn offsets(self, min: usize, max: usize) -> impl Iterator<Item = (isize, isize)> {
+        let r = max as isize;
+        (-r..=r)
+            .flat_map(move |dy| (-r..=r).map(move |dx| (dx, dy)))
+            .filter(move |&(dx, dy)| (min..=max).contains(&self.distance(dx, dy)))
+    }
+}
+
+/// Neighborhoods of any grid that can move a position by an offset, so `Grid` and
+/// `SparseGrid` share them. The iterators don't allocate.
+pub trait Neighbors {
+    type Pos: Copy;
+    type Value: Copy;
+
+    /// Position `(dx, dy)` away from `pos`, `None` when that is outside the grid.
+    fn offset_pos(&self, pos: Self::Pos, offset: (isize, isize)) -> Option<Self::Pos>;
+
+    fn value_at(&self, pos: Self::Pos) -> Self::Value;
+
+    /// In-bounds orthogonal neighbors of `pos` with their values, clockwise from up.
+    fn neighbors4(&self, pos: Self::Pos) -> impl Iterator<Item = (Self::Pos, Self::Value)> {
+        self.neighbors_at_offsets(pos, Direction::CARDINAL.into_iter().map(Direction::offset))
+    }
+
+    /// In-bounds orthogonal and diagonal neighbors of `pos` with their values, clockwise from up.
+    fn neighbors8(&self, pos: Self::Pos) -> impl Iterator<Item = (Self::Pos, Self::Value)> {
+        self.neighbors_at_offsets(pos, Direction::ALL.into_iter().map(Direction::offset))
+    }
+
+    /// In-bounds positions at a distance of `1..=radius` from `pos` with their values, row by row.
+    fn neighbors_within(&self, pos: Self::Pos, radius: usize, metric: Metric) -> impl Iterator<Item = (Self::Pos, Self::Value)> {
+        self.neighbors_at_offsets(pos, metric.offsets(1, radius))
+    }
+
+    /// In-bounds positions at exactly `distance` from `pos` with their values, row by row.
+    fn ring(&self, pos: Self::Pos, distance: usize, metric: Metric) -> impl Iterator<Item = (Self::Pos, Self::Value)> {
+        self.neighbors_at_offsets(pos, metric.offsets(distance.max(1), distance))
+    }
+
+    fn neighbors_at_offsets(
+        &self,
+        pos: Self::Pos,
+        offsets: impl Iterator<Item = (isize, isize)>,
+    ) -> impl Iterator<Item = (Self::Pos, Self::Value)> {
+        offsets.filter_map(move |offset| self.offset_pos(pos, offset)).map(|p| (p, self.value_at(p)))
+    }
+}
+
+/// What happens when a move leaves the grid: it either fails or comes back in on the opposite side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    #[default]
+    Bounded,
+    /// Leaving on the left or right wraps around, the top and bottom are edges.
+    WrapX,
+    /// Leaving at the top or bottom wraps around, the left and right are edges.
+    WrapY,
+    /// Both axes wrap.
+    Torus,
+}
+
+impl Topology {
+    fn wraps_x(self) -> bool {
+        matches!(self, Topology::WrapX | Topology::Torus)
+    }
+
+    fn wraps_y(self) -> bool {
+        matches!(self, Topology::WrapY | Topology::Torus)
+    }
+
+    /// The same topology with the axes swapped, for grids that are turned by 90 degrees.
+    pub fn transposed(self) -> Topology {
+        match self {
+            Topology::WrapX => Topology::WrapY,
+            Topology::WrapY => Topology::WrapX,
+            t => t,
+        }
+    }
+
+    /// Steps between `a` and `b` on an axis of length `len`, the shorter way around when it wraps.
+    fn distance_on_axis(wraps: bool, a: usize, b: usize, len: usize) -> usize {
+        let d = a.abs_diff(b);
+        if wraps { d.min(len - d) } else { d }
+    }
+
+    /// `coord + delta` on an axis of length `len`, `None` when it leaves a bounded axis.
+    fn move_on_axis(wraps: bool, coord: usize, delta: i128, len: usize) -> Option<usize> {
+        let moved = coord as i128 + delta;
+        if wraps {
+            Some(moved.rem_euclid(len as i128) as usize)
+        } else {
+            (0..len as i128).contains(&moved).then_some(moved as usize)
+        }
+    }
+}
+
+/// A cell of a grid, either a `usize` position or `(x, y)` coordinates.
+pub trait GridIndex: Copy {
+    /// Position in the flat storage, `None` when outside a `width` x `rows` grid.
+    fn to_pos(self, width: usize, rows: usize) -> Option<usize>;
+}
+
+impl GridIndex for usize {
+    fn to_pos(self, width: usize, rows: usize) -> Option<usize> {
+        (self < width * rows).then_some(self)
+    }
+}
+
+impl GridIndex for (usize, usize) {
+    fn to_pos(self, width: usize, rows: usize) -> Option<usize> {
+        let (x, y) = self;
+        (x < width && y < rows).then_some(y * width + x)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Grid<T: Copy> {
     grid: Vec<T>,
     array_width: usize,
     num_rows: usize,
+    topology: Topology,
 }
 
 impl<T: Copy> Grid<T> {
+    /// A `width` x `num_rows` grid with every cell set to `value`.
+    pub fn new(width: usize, num_rows: usize, value: T) -> Grid<T> {
+        Grid { grid: vec![value; width * num_rows], array_width: width, num_rows, topology: Topology::Bounded }
+    }
+
+    /// The grid with moves and neighborhoods following `topology`, grids start out bounded.
+    pub fn with_topology(mut self, topology: Topology) -> Grid<T> {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn width(&self) -> usize {
+        self.array_width
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Moves `pos` by `(dx, dy)`, `None` when that leaves a bounded side of the grid.
     pub fn move_pos(&self, pos: usize, coords: (isize, isize)) -> Option<usize> {
-        let (x, y) = coords;
-        let y_idx = (pos / self.array_width) as isize + y;
-        let x_idx = (pos % self.array_width) as isize + x;
+        self.advance(pos, coords, 1)
+    }
 
-        if (x_idx >= 0) && (x_idx < self.array_width as isize) && (y_idx >= 0) && (y_idx < self.num_rows as isize)
-        {
-            Some(y_idx as usize * self.array_width + x_idx as usize)
-        } else {
-            None
-        }
+    /// Where `pos` ends up after `n_steps` moves of `velocity`, computed in one go. On a bounded
+    /// axis the result is `None` if the last position is outside, which for a straight line means
+    /// the grid was left somewhere along the way.
+    pub fn advance(&self, pos: usize, velocity: (isize, isize), n_steps: usize) -> Option<usize> {
+        self.advance_in(self.topology, pos, velocity, n_steps)
+    }
+
+    fn advance_in(&self, topology: Topology, pos: usize, velocity: (isize, isize), n_steps: usize) -> Option<usize> {
+        let (x, y) = self.pos_to_coords(pos);
+        let (vx, vy) = velocity;
+        let x = Topology::move_on_axis(topology.wraps_x(), x, vx as i128 * n_steps as i128, self.array_width)?;
+        let y = Topology::move_on_axis(topology.wraps_y(), y, vy as i128 * n_steps as i128, self.num_rows)?;
+        Some(y * self.array_width + x)
+    }
+
+    /// Fewest orthogonal steps from `a` to `b`, going across the edge where the topology wraps.
+    pub fn manhattan_distance(&self, a: usize, b: usize) -> usize {
+        let ((ax, ay), (bx, by)) = (self.pos_to_coords(a), self.pos_to_coords(b));
+        Topology::distance_on_axis(self.topology.wraps_x(), ax, bx, self.array_width)
+            + Topology::distance_on_axis(self.topology.wraps_y(), ay, by, self.num_rows)
+    }
+
+    /// Position one step from `pos` in `direction`, `None` when that leaves the grid.
+    pub fn step(&self, pos: usize, direction: Direction) -> Option<usize> {
+        self.move_pos(pos, direction.offset())
+    }
+
+    /// Like `step` but stops at the edge whatever the topology, for walks along a row,
+    /// column or diagonal that must end.
+    pub fn step_within(&self, pos: usize, direction: Direction) -> Option<usize> {
+        self.advance_in(Topology::Bounded, pos, direction.offset(), 1)
     }
 
     pub fn len(&self) -> usize {
@@ -30,13 +307,53 @@ impl<T: Copy> Grid<T> {
     pub fn peek(&self, pos: usize) -> T {
         self.grid[pos]
     }
-    
-    fn parse_data_generic<R: BufRead>(reader: R, process_fun: &dyn Fn(char)->T) -> Grid<T> {
-        let mut file_iter = reader.lines().peekable();
-        let array_width = file_iter.peek().unwrap().as_ref().unwrap().len();
-        let grid = file_iter.map(|l| l.unwrap().chars().map(|c| process_fun(c)).collect::<Vec<_>>()).flatten().collect::<Vec<T>>();
-        let num_rows = grid.len() / array_width;
-        Grid { grid, array_width, num_rows} }
+
+
+    fn index_of<I: GridIndex>(&self, idx: I) -> Option<usize> {
+        idx.to_pos(self.array_width, self.num_rows)
+    }
+
+    fn expect_index<I: GridIndex>(&self, idx: I) -> usize {
+        self.index_of(idx).unwrap_or_else(|| {
+            panic!("index out of bounds for a {}x{} grid", self.array_width, self.num_rows)
+        })
+    }
+
+    /// Value at `idx`, `None` when it lies outside the grid.
+    pub fn get<I: GridIndex>(&self, idx: I) -> Option<T> {
+        self.index_of(idx).map(|pos| self.grid[pos])
+    }
+
+    pub fn get_mut<I: GridIndex>(&mut self, idx: I) -> Option<&mut T> {
+        self.index_of(idx).map(|pos| &mut self.grid[pos])
+    }
+
+    /// Replaces the value at `idx` and returns the previous one, panics outside the grid like indexing.
+    pub fn set<I: GridIndex>(&mut self, idx: I, value: T) -> T {
+        std::mem::replace(&mut self[idx], value)
+    }
+
+    pub fn swap<A: GridIndex, B: GridIndex>(&mut self, a: A, b: B) {
+        let (a, b) = (self.expect_index(a), self.expect_index(b));
+        self.grid.swap(a, b);
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.grid.fill(value);
+    }
+
+    /// Replaces every `from` with `to` and returns how many cells changed.
+    pub fn replace_all(&mut self, from: T, to: T) -> usize
+    where
+        T: PartialEq,
+    {
+        let mut count = 0;
+        for cell in self.grid.iter_mut().filter(|c| **c == from) {
+            *cell = to;
+            count += 1;
+        }
+        count
+    }
 
     pub fn pos_to_coords(&self, pos: usize) -> (usize, usize) {
         ((pos % self.array_width), (pos / self.array_width))
@@ -51,19 +368,103 @@ impl<T: Copy> Grid<T> {
     }
 }
 
-pub trait ParseData<T: Copy> {
-    fn parse_data<R: BufRead>(reader: R) -> Grid<T>;
+impl<T: Copy> Neighbors for Grid<T> {
+    type Pos = usize;
+    type Value = T;
+
+    fn offset_pos(&self, pos: usize, offset: (isize, isize)) -> Option<usize> {
+        self.move_pos(pos, offset)
+    }
+
+    fn value_at(&self, pos: usize) -> T {
+        self.grid[pos]
+    }
+}
+
+impl<T: Copy, I: GridIndex> Index<I> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, idx: I) -> &T {
+        &self.grid[self.expect_index(idx)]
+    }
+}
+
+impl<T: Copy, I: GridIndex> IndexMut<I> for Grid<T> {
+    fn index_mut(&mut self, idx: I) -> &mut T {
+        let pos = self.expect_index(idx);
+        &mut self.grid[pos]
+    }
+}
+
+/// A cell that is read from a single character, implement it for a tile enum to parse a `Grid<Tile>`.
+pub trait FromGridChar: Copy {
+    /// `None` when `c` is not a valid cell.
+    fn from_grid_char(c: char) -> Option<Self>;
 }
 
-impl ParseData<u32> for Grid<u32> {
-    fn parse_data<R: BufRead>(reader: R) -> Grid<u32> {
-        Grid::parse_data_generic(reader, &|c| c.to_digit(10).unwrap())
+impl FromGridChar for char {
+    fn from_grid_char(c: char) -> Option<char> {
+        Some(c)
     }
 }
 
-impl ParseData<char> for Grid<char> {
-    fn parse_data<R: BufRead>(reader: R) -> Grid<char> {
-        Grid::parse_data_generic(reader, &|c| c)
+/// Digit grids such as height maps.
+impl FromGridChar for u32 {
+    fn from_grid_char(c: char) -> Option<u32> {
+        c.to_digit(10)
+    }
+}
+
+impl FromGridChar for u8 {
+    fn from_grid_char(c: char) -> Option<u8> {
+        c.to_digit(10).map(|d| d as u8)
+    }
+}
+
+impl<T: FromGridChar> Grid<T> {
+    /// Parses a grid that ends at a blank line or at the end of the input, anything after
+    /// the blank line is ignored. Use `parse_section` when the rest of the input is needed.
+    pub fn parse<R: BufRead>(reader: R) -> Result<Grid<T>> {
+        Grid::parse_section(reader).map(|(grid, _)| grid)
+    }
+
+    /// Parses a grid up to and including the next blank line and hands back the reader
+    /// positioned at the following section. Every row must be as wide as the first one,
+    /// error line numbers count from the current position of the reader.
+    pub fn parse_section<R: BufRead>(mut reader: R) -> Result<(Grid<T>, R)> {
+        let mut grid = vec![];
+        let mut array_width = 0;
+        let mut num_rows = 0;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let row = line.trim_end_matches('\n').trim_end_matches('\r');
+            if row.is_empty() {
+                break;
+            }
+            let line_no = num_rows + 1;
+            let mut width = 0;
+            for (idx, c) in row.chars().enumerate() {
+                let cell = T::from_grid_char(c)
+                    .ok_or_else(|| Error::at(line_no, idx + 1, format!("invalid grid cell `{}`", c)))?;
+                grid.push(cell);
+                width += 1;
+            }
+            if num_rows == 0 {
+                array_width = width;
+            } else if width != array_width {
+                let column = width.min(array_width) + 1;
+                return Err(Error::at(line_no, column, format!("row has {} cells, expected {}", width, array_width)));
+            }
+            num_rows += 1;
+        }
+        if num_rows == 0 {
+            return Err(Error::invalid("empty grid"));
+        }
+        Ok((Grid { grid, array_width, num_rows, topology: Topology::Bounded }, reader))
     }
 }
 
@@ -71,9 +472,164 @@ impl<T: Copy + Display> Display for Grid<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut row_start = 0;
         while row_start < self.len() {
-            write!(f, "{}\n", &self.grid[row_start..row_start + self.array_width].iter().map(|d| d.to_string()).collect::<String>())?;
+            writeln!(f, "{}", &self.grid[row_start..row_start + self.array_width].iter().map(|d| d.to_string()).collect::<String>())?;
             row_start += self.array_width;
         }
-        write!(f, "\n")
+        writeln!(f)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.turn_right_45(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        for direction in Direction::ALL {
+            let (dx, dy) = direction.offset();
+            assert_eq!(direction.opposite().offset(), (-dx, -dy));
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.is_diagonal(), dx != 0 && dy != 0);
+        }
+        assert_eq!("^>v<".chars().map(Direction::from_arrow).collect::<Vec<_>>(), Direction::CARDINAL.map(Some));
+        assert_eq!(Direction::from_arrow('.'), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::<u32>::parse("123\n456\n789\n".as_bytes()).unwrap();
+        let values = |it: &mut dyn Iterator<Item = (usize, u32)>| it.map(|(_, v)| v).collect::<Vec<_>>();
+        assert_eq!(values(&mut grid.neighbors4(4)), vec![2, 6, 8, 4]);
+        assert_eq!(values(&mut grid.neighbors4(0)), vec![2, 4]);
+        assert_eq!(values(&mut grid.neighbors8(4)), vec![2, 3, 6, 9, 8, 7, 4, 1]);
+        assert_eq!(grid.neighbors8(8).collect::<Vec<_>>(), vec![(5, 6), (7, 8), (4, 5)]);
+        assert_eq!(values(&mut grid.neighbors_within(0, 2, Metric::Manhattan)), vec![2, 3, 4, 5, 7]);
+        assert_eq!(values(&mut grid.neighbors_within(0, 2, Metric::Chebyshev)), vec![2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(values(&mut grid.ring(0, 2, Metric::Manhattan)), vec![3, 5, 7]);
+        assert_eq!(values(&mut grid.ring(4, 1, Metric::Chebyshev)), values(&mut grid.neighbors_within(4, 1, Metric::Chebyshev)));
+    }
+
+    #[test]
+    fn test_mutation() {
+        let mut grid = Grid::<char>::parse("ab.\n.c.\n".as_bytes()).unwrap();
+        assert_eq!(grid.get(1), Some('b'));
+        assert_eq!(grid.get((1, 1)), Some('c'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get(6), None);
+        assert_eq!(grid[(0, 0)], 'a');
+        assert_eq!(grid[4], 'c');
+
+        assert_eq!(grid.set((2, 1), '#'), '.');
+        grid[(2, 0)] = '#';
+        *grid.get_mut(3).unwrap() = 'd';
+        grid.swap(0, (1, 1));
+        assert_eq!(grid.to_string(), "cb#\nda#\n\n");
+        assert_eq!(grid.replace_all('#', '.'), 2);
+        assert_eq!(grid.get_mut((0, 2)), None);
+        grid.fill('x');
+        assert_eq!(grid.to_string(), "xxx\nxxx\n\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds for a 3x2 grid")]
+    fn test_index_out_of_bounds() {
+        let grid = Grid::<char>::parse("ab.\n.c.\n".as_bytes()).unwrap();
+        let _ = grid[(3, 0)];
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    impl FromGridChar for Tile {
+        fn from_grid_char(c: char) -> Option<Tile> {
+            match c {
+                '.' => Some(Tile::Open),
+                '#' => Some(Tile::Wall),
+                _ => None,
+            }
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::<Tile>::parse(".#\n#.\n".as_bytes()).unwrap();
+        assert_eq!(grid.get_grid(), &vec![Tile::Open, Tile::Wall, Tile::Wall, Tile::Open]);
+        let err = Grid::<Tile>::parse(".#\n#.\n.x\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 2: invalid grid cell `x`");
+        let err = Grid::<u32>::parse("12\n3.\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: invalid grid cell `.`");
+        assert!(Grid::<char>::parse("".as_bytes()).is_err());
+        assert!(Grid::<char>::parse("\nab\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let err = Grid::<char>::parse("abc\nab\nabc\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: row has 2 cells, expected 3");
+        let err = Grid::<char>::parse("ab\nabc\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: row has 3 cells, expected 2");
+
+        let grid = Grid::<char>::parse("ab\r\ncd\r\n".as_bytes()).unwrap();
+        assert_eq!(grid.to_string(), "ab\ncd\n\n");
+        let grid = Grid::<char>::parse("ab\ncd".as_bytes()).unwrap();
+        assert_eq!(grid.get((1, 1)), Some('d'));
+    }
+
+    #[test]
+    fn test_parse_section() {
+        let (grid, mut rest) = Grid::<char>::parse_section("#.\r\n.#\r\n\r\n<>^\n".as_bytes()).unwrap();
+        assert_eq!(grid.len(), 4);
+        let mut moves = String::new();
+        rest.read_to_string(&mut moves).unwrap();
+        assert_eq!(moves, "<>^\n");
+        assert_eq!(Grid::<char>::parse("#.\n\n<>^\n".as_bytes()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_topology() {
+        let grid = Grid::new(11, 7, '.');
+        let start = grid.coords_to_pos(2, 4);
+        assert_eq!(grid.advance(start, (2, -3), 5), None);
+        assert_eq!(grid.move_pos(0, (-1, 0)), None);
+
+        let torus = grid.clone().with_topology(Topology::Torus);
+        // a robot at 2,4 moving 2,-3 per second is at 1,3 after five seconds
+        assert_eq!(torus.advance(start, (2, -3), 5), Some(torus.coords_to_pos(1, 3)));
+        let stepped = (0..5).try_fold(start, |pos, _| torus.move_pos(pos, (2, -3)));
+        assert_eq!(stepped, Some(torus.coords_to_pos(1, 3)));
+        assert_eq!(torus.advance(start, (2, -3), 0), Some(start));
+        assert_eq!(torus.advance(start, (-7, 5), 1_000_000_000_000), torus.advance(start, (-7, 5), 1_000_000_000_000 % 77));
+        assert_eq!(torus.neighbors8(0).count(), 8);
+
+        let wrap_x = grid.clone().with_topology(Topology::WrapX);
+        assert_eq!(wrap_x.step(0, Direction::Left), Some(10));
+        assert_eq!(wrap_x.step(0, Direction::Up), None);
+        assert_eq!(wrap_x.neighbors4(0).map(|(p, _)| p).collect::<Vec<_>>(), vec![1, 11, 10]);
+
+        assert_eq!(grid.manhattan_distance(0, 10), 10);
+        assert_eq!(wrap_x.manhattan_distance(0, 10), 1);
+        assert_eq!(wrap_x.manhattan_distance(0, 76), 7);
+        assert_eq!(torus.manhattan_distance(0, 76), 2);
+
+        let wrap_y = grid.with_topology(Topology::WrapY);
+        assert_eq!(wrap_y.step(0, Direction::Up), Some(66));
+        assert_eq!(wrap_y.step(0, Direction::Left), None);
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = Grid::<char>::parse("abc\ndef\n".as_bytes()).unwrap();
+        assert_eq!(grid.step(0, Direction::Right), Some(1));
+        assert_eq!(grid.step(0, Direction::DownRight), Some(4));
+        assert_eq!(grid.step(0, Direction::Up), None);
+        assert_eq!(grid.step(5, Direction::Right), None);
+        assert_eq!(grid.step(5, Direction::UpLeft), Some(1));
+    }
+}
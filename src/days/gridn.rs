@@ -0,0 +1,297 @@
+use std::fmt::{self, Display};
+
+use crate::days::grid::Grid;
+
+/// Coordinates of a `GridN`, axis 0 is x, axis 1 is y and so on.
+pub type Coords<const D: usize> = [i64; D];
+
+/// Dense grid with `D` axes in one flat `Vec`, axis 0 varying fastest. Coordinates are signed
+/// and the bounds grow when a cell outside of them is set, which suits cellular automata that
+/// spread in every direction. Cells outside the bounds read as `default`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridN<T: Copy, const D: usize> {
+    cells: Vec<T>,
+    /// Coordinates of the cell at position 0.
+    min: Coords<D>,
+    dims: [usize; D],
+    default: T,
+}
+
+impl<T: Copy, const D: usize> GridN<T, D> {
+    /// A grid of `dims` cells with its smallest corner at the origin, all set to `default`.
+    pub fn new(dims: [usize; D], default: T) -> GridN<T, D> {
+        GridN { cells: vec![default; dims.iter().product()], min: [0; D], dims, default }
+    }
+
+    /// Number of cells along each axis.
+    pub fn dims(&self) -> [usize; D] {
+        self.dims
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Smallest and largest coordinates inside the grid, `None` while it has no cells.
+    pub fn bounds(&self) -> Option<(Coords<D>, Coords<D>)> {
+        (!self.is_empty()).then(|| (self.min, std::array::from_fn(|axis| self.min[axis] + self.dims[axis] as i64 - 1)))
+    }
+
+    pub fn pos_to_coords(&self, pos: usize) -> Coords<D> {
+        let mut rest = pos;
+        std::array::from_fn(|axis| {
+            let coord = rest % self.dims[axis];
+            rest /= self.dims[axis];
+            self.min[axis] + coord as i64
+        })
+    }
+
+    /// Position of `coords` in the flat storage, `None` outside the bounds.
+    pub fn coords_to_pos(&self, coords: Coords<D>) -> Option<usize> {
+        let mut pos = 0;
+        for axis in (0..D).rev() {
+            let offset = coords[axis] - self.min[axis];
+            if !(0..self.dims[axis] as i64).contains(&offset) {
+                return None;
+            }
+            pos = pos * self.dims[axis] + offset as usize;
+        }
+        Some(pos)
+    }
+
+    pub fn get(&self, coords: Coords<D>) -> T {
+        self.coords_to_pos(coords).map_or(self.default, |pos| self.cells[pos])
+    }
+
+    /// Stores `value` at `coords`, growing the bounds to include it, and returns the previous value.
+    pub fn set(&mut self, coords: Coords<D>, value: T) -> T {
+        if self.coords_to_pos(coords).is_none() {
+            let (min, max) = match self.bounds() {
+                Some((min, max)) => (
+                    std::array::from_fn(|axis| min[axis].min(coords[axis])),
+                    std::array::from_fn(|axis| max[axis].max(coords[axis])),
+                ),
+                None => (coords, coords),
+            };
+            self.resize(min, max);
+        }
+        let pos = self.coords_to_pos(coords).unwrap();
+        std::mem::replace(&mut self.cells[pos], value)
+    }
+
+    /// Adds `margin` cells of `default` on both sides of every axis, e.g. before each
+    /// generation of an automaton whose cells can come alive just outside.
+    pub fn grow(&mut self, margin: usize) {
+        if let Some((min, max)) = self.bounds() {
+            let margin = margin as i64;
+            self.resize(min.map(|c| c - margin), max.map(|c| c + margin));
+        }
+    }
+
+    /// Moves the cells into new bounds from `min` to `max`, both included, which must contain the old ones.
+    fn resize(&mut self, min: Coords<D>, max: Coords<D>) {
+        let dims = std::array::from_fn(|axis| (max[axis] - min[axis] + 1) as usize);
+        let mut grown = GridN { cells: vec![self.default; dims.iter().product()], min, dims, default: self.default };
+        for (coords, value) in self.iter() {
+            let pos = grown.coords_to_pos(coords).expect("resized grid must contain the old bounds");
+            grown.cells[pos] = value;
+        }
+        *self = grown;
+    }
+
+    /// Every cell with its coordinates, in storage order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coords<D>, T)> + '_ {
+        self.cells.iter().enumerate().map(|(pos, &value)| (self.pos_to_coords(pos), value))
+    }
+
+    /// Offsets to the 3^D - 1 cells around a cell, diagonals included. Each offset is worked out
+    /// from its index in base 3, so nothing is allocated.
+    pub fn neighbor_offsets() -> impl Iterator<Item = Coords<D>> {
+        let count = 3usize.pow(D as u32);
+        // the index whose digits are all 1 is the offset `[0; D]` of the cell itself
+        let center = count / 2;
+        (0..count).filter(move |&n| n != center).map(|n| {
+            let mut rest = n;
+            std::array::from_fn(|_| {
+                let offset = (rest % 3) as i64 - 1;
+                rest /= 3;
+                offset
+            })
+        })
+    }
+
+    /// The 3^D - 1 cells around `coords` with their values, cells outside the bounds read as `default`.
+    pub fn neighbors(&self, coords: Coords<D>) -> impl Iterator<Item = (Coords<D>, T)> + '_ {
+        GridN::<T, D>::neighbor_offsets().map(move |offset| {
+            let next = std::array::from_fn(|axis| coords[axis] + offset[axis]);
+            (next, self.get(next))
+        })
+    }
+
+    /// The 2 * D cells that share a face with `coords`.
+    pub fn orthogonal_neighbors(&self, coords: Coords<D>) -> impl Iterator<Item = (Coords<D>, T)> + '_ {
+        (0..D).flat_map(move |axis| {
+            [-1, 1].into_iter().map(move |delta| {
+                let mut next = coords;
+                next[axis] += delta;
+                (next, self.get(next))
+            })
+        })
+    }
+
+    /// The plane spanned by axes `across` and `down` through `at` as a 2-D grid, `across`
+    /// becomes x and `down` becomes y. Coordinates of `at` on those two axes are ignored.
+    pub fn slice(&self, across: usize, down: usize, at: Coords<D>) -> Grid<T> {
+        assert!(across < D && down < D && across != down, "can't slice a {}-D grid along axes {} and {}", D, across, down);
+        let mut grid = Grid::new(self.dims[across], self.dims[down], self.default);
+        for y in 0..self.dims[down] {
+            for x in 0..self.dims[across] {
+                let mut coords = at;
+                coords[across] = self.min[across] + x as i64;
+                coords[down] = self.min[down] + y as i64;
+                grid[(x, y)] = self.get(coords);
+            }
+        }
+        grid
+    }
+
+    /// A 2-D grid as the plane of a `GridN` where every axis after x and y is 0.
+    pub fn from_grid(grid: &Grid<T>, default: T) -> GridN<T, D> {
+        assert!(D >= 2, "a 2-D grid doesn't fit into a {}-D grid", D);
+        let mut dims = [1; D];
+        dims[0] = grid.width();
+        dims[1] = grid.num_rows();
+        GridN { cells: grid.get_grid().clone(), min: [0; D], dims, default }
+    }
+}
+
+impl<T: Copy + Display, const D: usize> Display for GridN<T, D> {
+    /// Every x/y plane, headed by its coordinates on the other axes like `z=-1, w=0`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const NAMES: [&str; 4] = ["x", "y", "z", "w"];
+        if D < 2 {
+            let line = self.cells.iter().map(|v| v.to_string()).collect::<String>();
+            return writeln!(f, "{}", line);
+        }
+        let plane = self.dims[0] * self.dims[1];
+        for start in (0..self.len()).step_by(plane.max(1)) {
+            let coords = self.pos_to_coords(start);
+            if D > 2 {
+                let header = (2..D)
+                    .map(|axis| format!("{}={}", NAMES.get(axis).map_or_else(|| format!("axis{}", axis), |n| n.to_string()), coords[axis]))
+                    .collect::<Vec<_>>();
+                writeln!(f, "{}", header.join(", "))?;
+            }
+            write!(f, "{}", self.slice(0, 1, coords))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Conway Cubes: active cells stay active with 2 or 3 active neighbors, inactive ones
+    /// become active with exactly 3.
+    fn cycle<const D: usize>(grid: &GridN<bool, D>) -> GridN<bool, D> {
+        let mut next = grid.clone();
+        next.grow(1);
+        let current = next.clone();
+        for (coords, active) in current.iter() {
+            let around = current.neighbors(coords).filter(|&(_, n)| n).count();
+            next.set(coords, matches!((active, around), (true, 2) | (_, 3)));
+        }
+        next
+    }
+
+    trait MapDisplay {
+        fn map_display(&self) -> String;
+    }
+
+    impl MapDisplay for Grid<bool> {
+        fn map_display(&self) -> String {
+            self.rows().map(|row| row.map(|(_, active)| if active { '#' } else { '.' }).chain(['\n']).collect::<String>()).collect()
+        }
+    }
+
+    fn cubes<const D: usize>() -> GridN<bool, D> {
+        let grid = Grid::<char>::parse(".#.\n..#\n###\n".as_bytes()).unwrap();
+        let mut cubes = GridN::new([0; D], false);
+        for (pos, &c) in grid.get_grid().iter().enumerate() {
+            let (x, y) = grid.pos_to_coords(pos);
+            let mut coords = [0; D];
+            coords[0] = x as i64;
+            coords[1] = y as i64;
+            cubes.set(coords, c == '#');
+        }
+        cubes
+    }
+
+    #[test]
+    fn test_coords() {
+        let mut grid = GridN::new([2, 3, 4], 0);
+        assert_eq!(grid.len(), 24);
+        assert_eq!(grid.pos_to_coords(7), [1, 0, 1]);
+        assert_eq!(grid.coords_to_pos([1, 0, 1]), Some(7));
+        assert_eq!(grid.coords_to_pos([2, 0, 0]), None);
+        for pos in 0..grid.len() {
+            assert_eq!(grid.coords_to_pos(grid.pos_to_coords(pos)), Some(pos));
+        }
+
+        assert_eq!(grid.set([1, 2, 3], 5), 0);
+        assert_eq!(grid.set([-1, 0, 0], 7), 0);
+        assert_eq!(grid.bounds(), Some(([-1, 0, 0], [1, 2, 3])));
+        assert_eq!(grid.get([1, 2, 3]), 5);
+        assert_eq!(grid.get([-1, 0, 0]), 7);
+        assert_eq!(grid.get([9, 9, 9]), 0);
+        grid.grow(1);
+        assert_eq!(grid.dims(), [5, 5, 6]);
+        assert_eq!(grid.get([1, 2, 3]), 5);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        assert_eq!(GridN::<u8, 2>::neighbor_offsets().count(), 8);
+        assert_eq!(GridN::<u8, 3>::neighbor_offsets().count(), 26);
+        assert_eq!(GridN::<u8, 4>::neighbor_offsets().count(), 80);
+        assert!(GridN::<u8, 3>::neighbor_offsets().all(|offset| offset != [0; 3]));
+        let mut grid = GridN::new([3, 3, 3], 0);
+        let cells = grid.iter().map(|(coords, _)| coords).collect::<Vec<_>>();
+        for coords in cells {
+            grid.set(coords, 1);
+        }
+        assert_eq!(grid.neighbors([1, 1, 1]).map(|(_, v)| v).sum::<u8>(), 26);
+        assert_eq!(grid.neighbors([0, 0, 0]).map(|(_, v)| v).sum::<u8>(), 7);
+        assert_eq!(grid.orthogonal_neighbors([0, 0, 0]).map(|(_, v)| v).sum::<u8>(), 3);
+    }
+
+    #[test]
+    fn test_conway_cubes() {
+        let mut grid = cubes::<3>();
+        grid = cycle(&grid);
+        assert_eq!(grid.iter().filter(|&(_, active)| active).count(), 11);
+        let z_minus = grid.slice(0, 1, [0, 0, -1]).map_display();
+        assert_eq!(z_minus, ".....\n.....\n.#...\n...#.\n..#..\n");
+        for _ in 1..6 {
+            grid = cycle(&grid);
+        }
+        assert_eq!(grid.iter().filter(|&(_, active)| active).count(), 112);
+
+        let hyper = cycle(&cubes::<4>());
+        assert_eq!(hyper.iter().filter(|&(_, active)| active).count(), 29);
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::<char>::parse("ab\ncd\n".as_bytes()).unwrap();
+        let mut cube = GridN::<char, 3>::from_grid(&grid, '.');
+        cube.set([0, 0, 1], 'e');
+        assert_eq!(cube.to_string(), "z=0\nab\ncd\n\nz=1\ne.\n..\n\n");
+        assert_eq!(cube.slice(0, 2, [0, 1, 0]).to_string(), "cd\n..\n\n");
+    }
+}
@@ -0,0 +1,259 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::days::grid::Grid;
+
+/// 8-bit red, green and blue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+    pub const GREEN: Rgb = Rgb(0, 255, 0);
+    pub const BLUE: Rgb = Rgb(0, 0, 255);
+    pub const YELLOW: Rgb = Rgb(255, 255, 0);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+
+    /// Shade between black at `0` and white at `max`, for heights and distances.
+    pub fn gray(value: u64, max: u64) -> Rgb {
+        let v = (value.min(max) * 255 / max.max(1)) as u8;
+        Rgb(v, v, v)
+    }
+}
+
+/// Positions drawn over the grid in a single color, e.g. a path or the visited cells.
+/// Later overlays are drawn on top of earlier ones.
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    positions: Vec<usize>,
+    color: Rgb,
+}
+
+impl Overlay {
+    pub fn new(positions: impl IntoIterator<Item = usize>, color: Rgb) -> Overlay {
+        Overlay { positions: positions.into_iter().collect(), color }
+    }
+
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    pub fn color(&self) -> Rgb {
+        self.color
+    }
+}
+
+/// Pixels in rows from the top left, encodes to PPM or PNG.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Binary PPM (`P6`), which most image viewers open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        out
+    }
+
+    /// Truecolor PNG. The image data is stored without compression, which keeps the
+    /// encoder short and is fine for pictures of puzzle maps.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            // every scanline starts with its filter type, 0 is none
+            raw.push(0);
+            raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // bit depth 8, color type 2 (RGB), default compression, filtering and no interlace
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    /// Writes the image, the format follows the extension of `path`: `.ppm` or `.png`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()).as_deref() {
+            Some("ppm") => self.to_ppm(),
+            Some("png") => self.to_png(),
+            _ => {
+                let message = format!("{} is neither a .ppm nor a .png file", path.display());
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+            }
+        };
+        fs::write(path, bytes)
+    }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// zlib stream made of uncompressed deflate blocks, each holding at most 65535 bytes.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+impl<T: Copy> Grid<T> {
+    /// The grid as an image with `scale` x `scale` pixels per cell, colored by `palette`
+    /// and then by the overlays. Panics when `scale` is 0, which would give an empty image.
+    pub fn to_image(&self, palette: impl Fn(T) -> Rgb, scale: usize, overlays: &[Overlay]) -> Image {
+        assert!(scale > 0, "an image needs a scale of at least 1 pixel per cell");
+        let mut cells = self.get_grid().iter().map(|&v| palette(v)).collect::<Vec<_>>();
+        for overlay in overlays {
+            for &pos in &overlay.positions {
+                if let Some(cell) = cells.get_mut(pos) {
+                    *cell = overlay.color;
+                }
+            }
+        }
+
+        let (width, height) = (self.width() * scale, self.num_rows() * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let row = y / scale * self.width();
+            pixels.extend((0..width).map(|x| cells[row + x / scale]));
+        }
+        Image { width, height, pixels }
+    }
+
+    /// Writes the grid to a `.ppm` or `.png` file, see `to_image`.
+    pub fn render_image(&self, path: impl AsRef<Path>, palette: impl Fn(T) -> Rgb, scale: usize) -> io::Result<()> {
+        self.to_image(palette, scale, &[]).save(path)
+    }
+
+    /// Like `render_image` with overlays drawn over the cells.
+    pub fn render_image_with(
+        &self,
+        path: impl AsRef<Path>,
+        palette: impl Fn(T) -> Rgb,
+        scale: usize,
+        overlays: &[Overlay],
+    ) -> io::Result<()> {
+        self.to_image(palette, scale, overlays).save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette(c: char) -> Rgb {
+        if c == '#' { Rgb::BLACK } else { Rgb::WHITE }
+    }
+
+    #[test]
+    fn test_to_image() {
+        let grid = Grid::<char>::parse("#.\n..\n".as_bytes()).unwrap();
+        let image = grid.to_image(palette, 2, &[Overlay::new([3], Rgb::RED)]);
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.pixel(1, 1), Rgb::BLACK);
+        assert_eq!(image.pixel(2, 1), Rgb::WHITE);
+        assert_eq!(image.pixel(3, 3), Rgb::RED);
+        assert_eq!(&image.to_ppm()[..11], b"P6\n4 4\n255\n");
+        assert_eq!(image.to_ppm().len(), 11 + 4 * 4 * 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "an image needs a scale of at least 1 pixel per cell")]
+    fn test_zero_scale() {
+        Grid::new(2, 2, '.').to_image(palette, 0, &[]);
+    }
+
+    #[test]
+    fn test_png() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        let grid = Grid::new(300, 300, '.');
+        let png = grid.to_image(palette, 1, &[]).to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 1, 44, 0, 0, 1, 44]);
+        assert_eq!(&png[png.len() - 12..], &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+        // 300 scanlines of 901 bytes need five stored blocks
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(idat_len, 2 + 5 * 5 + 300 * 901 + 4);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir();
+        let grid = Grid::<char>::parse("#.\n".as_bytes()).unwrap();
+        let path = dir.join(format!("aoc-image-{}.ppm", std::process::id()));
+        grid.render_image(&path, palette, 1).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"P6\n2 1\n255\n\0\0\0\xff\xff\xff");
+        fs::remove_file(&path).unwrap();
+        assert!(grid.render_image(dir.join("grid.txt"), palette, 1).is_err());
+    }
+}
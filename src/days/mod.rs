@@ -1,12 +1,56 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
+use crate::solution::Day;
+
+/// Declares the day modules and builds the registry the runner walks.
+/// A new day only needs a `Solution` impl and a line here.
+macro_rules! register_days {
+    ($($module:ident => $solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub fn registry() -> Vec<Day> {
+            let mut days = vec![$(Day::of::<$module::$solution>()),*];
+            days.sort_by_key(|d| (d.year, d.day));
+            days
+        }
+    };
+}
+
+register_days! {
+    day01 => Day01,
+    day02 => Day02,
+    day03 => Day03,
+    day04 => Day04,
+    day05 => Day05,
+    day06 => Day06,
+    day07 => Day07,
+    day08 => Day08,
+    day09 => Day09,
+    day10 => Day10,
+    day11 => Day11,
+}
+
+// shared helpers for the days, not every day uses every part of it
+#[allow(dead_code)]
+pub mod bitset;
+#[allow(dead_code)]
 pub mod grid;
+#[allow(dead_code)]
+pub mod gridn;
+#[allow(dead_code)]
+pub mod image;
+#[allow(dead_code)]
+pub mod pathfinding;
+#[allow(dead_code)]
+pub mod pattern;
+#[allow(dead_code)]
+pub mod regions;
+#[allow(dead_code)]
+pub mod sparse;
+#[allow(dead_code)]
+pub mod terminal;
+#[allow(dead_code)]
+pub mod transform;
+
+#[cfg(test)]
+pub fn open_input(day: u32) -> std::io::Cursor<Vec<u8>> {
+    std::io::Cursor::new(crate::cli::InputSource::for_day(day).read().expect("file not found"))
+}
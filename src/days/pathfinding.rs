@@ -0,0 +1,310 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::days::grid::{Direction, Grid, Neighbors};
+
+/// Distances and shortest-path predecessors found by a search.
+/// A state keeps every predecessor it can be reached from at its shortest distance,
+/// so the predecessors form a graph of all shortest paths back to the starts.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    dist: HashMap<S, u64>,
+    prev: HashMap<S, Vec<S>>,
+}
+
+impl<S: Copy + Eq + Hash> Search<S> {
+    fn new(starts: impl IntoIterator<Item = S>) -> Search<S> {
+        Search {
+            dist: starts.into_iter().map(|s| (s, 0)).collect(),
+            prev: HashMap::new(),
+        }
+    }
+
+    /// Keeps `to` at `dist` if that is no worse than what is known, returns true if it improved.
+    fn relax(&mut self, from: S, to: S, dist: u64) -> bool {
+        match self.dist.get(&to) {
+            Some(&known) if known < dist => false,
+            Some(&known) if known == dist => {
+                let prev = self.prev.entry(to).or_default();
+                if !prev.contains(&from) {
+                    prev.push(from);
+                }
+                false
+            }
+            _ => {
+                self.dist.insert(to, dist);
+                self.prev.insert(to, vec![from]);
+                true
+            }
+        }
+    }
+
+    pub fn distance(&self, state: S) -> Option<u64> {
+        self.dist.get(&state).copied()
+    }
+
+    /// Every state that was reached, starts included.
+    pub fn reached(&self) -> impl Iterator<Item = (S, u64)> + '_ {
+        self.dist.iter().map(|(&s, &d)| (s, d))
+    }
+
+    /// States right before `state` on its shortest paths, empty for starts and unreached states.
+    pub fn predecessors(&self, state: S) -> &[S] {
+        self.prev.get(&state).map_or(&[], |p| p.as_slice())
+    }
+
+    /// One shortest path from a start to `state`, both ends included.
+    pub fn path_to(&self, state: S) -> Option<Vec<S>> {
+        self.distance(state)?;
+        let mut path = vec![state];
+        while let Some(&prev) = self.predecessors(*path.last().unwrap()).first() {
+            path.push(prev);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state that lies on some shortest path to `state`, `state` included.
+    pub fn on_shortest_paths(&self, state: S) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut todo = vec![state];
+        while let Some(s) = todo.pop() {
+            if self.dist.contains_key(&s) && seen.insert(s) {
+                todo.extend_from_slice(self.predecessors(s));
+            }
+        }
+        seen
+    }
+
+    /// Number of distinct shortest paths from the starts to every reached state, assumes moves cost more than 0.
+    pub fn path_counts(&self) -> HashMap<S, u64> {
+        let mut states = self.reached().collect::<Vec<_>>();
+        states.sort_by_key(|&(_, d)| d);
+        let mut counts = HashMap::with_capacity(states.len());
+        for (state, _) in states {
+            let count = match self.predecessors(state) {
+                [] => 1,
+                prev => prev.iter().map(|p| counts[p]).sum(),
+            };
+            counts.insert(state, count);
+        }
+        counts
+    }
+}
+
+/// Breadth-first search where every move costs 1, explores everything reachable from `starts`.
+pub fn bfs<S, I>(starts: impl IntoIterator<Item = S>, mut successors: impl FnMut(S) -> I) -> Search<S>
+where
+    S: Copy + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(starts);
+    let mut queue = search.dist.keys().copied().collect::<VecDeque<_>>();
+    while let Some(state) = queue.pop_front() {
+        let dist = search.dist[&state] + 1;
+        for next in successors(state) {
+            if search.relax(state, next, dist) {
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra over `(state, cost)` successors, explores everything reachable from `starts`.
+pub fn dijkstra<S, I>(starts: impl IntoIterator<Item = S>, successors: impl FnMut(S) -> I) -> Search<S>
+where
+    S: Copy + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    best_first(starts, successors, |_| 0, |_| false).0
+}
+
+/// A* towards the first state for which `is_goal` holds. `heuristic` must never overestimate
+/// the remaining cost. Returns the goal that was reached, the search keeps all shortest paths to it.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(S) -> I,
+    heuristic: impl Fn(S) -> u64,
+    is_goal: impl Fn(S) -> bool,
+) -> Option<(S, Search<S>)>
+where
+    S: Copy + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let (search, goal) = best_first(starts, successors, heuristic, is_goal);
+    goal.map(|goal| (goal, search))
+}
+
+/// Shared loop of Dijkstra and A*. After the first goal is settled, states that can still
+/// reach it at the same cost are expanded so that its predecessors are complete.
+fn best_first<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(S) -> I,
+    heuristic: impl Fn(S) -> u64,
+    is_goal: impl Fn(S) -> bool,
+) -> (Search<S>, Option<S>)
+where
+    S: Copy + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut search = Search::new(starts);
+    // the heap holds indices into `states` so that states don't need to be `Ord`
+    let mut states = search.dist.keys().copied().collect::<Vec<_>>();
+    let mut heap = states.iter().enumerate().map(|(idx, &s)| Reverse((heuristic(s), idx))).collect::<BinaryHeap<_>>();
+    let mut goal = None;
+    while let Some(Reverse((estimate, idx))) = heap.pop() {
+        let state = states[idx];
+        let dist = search.dist[&state];
+        if dist + heuristic(state) < estimate {
+            continue;
+        }
+        match goal {
+            Some((_, best)) if estimate > best => break,
+            None if is_goal(state) => goal = Some((state, dist)),
+            _ => {}
+        }
+        for (next, cost) in successors(state) {
+            if search.relax(state, next, dist + cost) {
+                heap.push(Reverse((dist + cost + heuristic(next), states.len())));
+                states.push(next);
+            }
+        }
+    }
+    (search, goal.map(|(state, _)| state))
+}
+
+impl<T: Copy> Grid<T> {
+    /// Breadth-first search over orthogonal moves, `passable(from, to)` gets the values of both cells.
+    pub fn bfs(&self, start: usize, passable: impl Fn(T, T) -> bool) -> Search<usize> {
+        let passable = &passable;
+        bfs([start], |pos| {
+            let from = self[pos];
+            self.neighbors4(pos).filter(move |&(_, to)| passable(from, to)).map(|(next, _)| next)
+        })
+    }
+
+    /// Dijkstra over orthogonal moves, `cost(from, to)` returns `None` for moves that are not allowed.
+    pub fn dijkstra(&self, start: usize, cost: impl Fn(T, T) -> Option<u64>) -> Search<usize> {
+        dijkstra([start], |pos| self.weighted_neighbors(pos, &cost))
+    }
+
+    /// A* over orthogonal moves from `start` to `goal` with the Manhattan distance as heuristic,
+    /// which is only correct when every allowed move costs at least 1. On wrapping grids the
+    /// distance takes the shorter way around each wrapping axis.
+    pub fn astar(&self, start: usize, goal: usize, cost: impl Fn(T, T) -> Option<u64>) -> Option<Search<usize>> {
+        let heuristic = |pos| self.manhattan_distance(pos, goal) as u64;
+        astar([start], |pos| self.weighted_neighbors(pos, &cost), heuristic, |pos| pos == goal).map(|(_, search)| search)
+    }
+
+    fn weighted_neighbors<'a>(&'a self, pos: usize, cost: &'a impl Fn(T, T) -> Option<u64>) -> impl Iterator<Item = (usize, u64)> + 'a {
+        let from = self[pos];
+        self.neighbors4(pos).filter_map(move |(next, to)| Some((next, cost(from, to)?)))
+    }
+
+    /// Moves of a state that also has a facing: step forward in `direction` or turn in place.
+    /// Costs come from `cost(state, next)`, `None` forbids the move, so facing dependent puzzles
+    /// can run `dijkstra` or `astar` over `(pos, Direction)` states.
+    pub fn facing_moves(
+        &self,
+        (pos, direction): (usize, Direction),
+        cost: impl Fn((usize, Direction), (usize, Direction)) -> Option<u64>,
+    ) -> Vec<((usize, Direction), u64)> {
+        let forward = self.step(pos, direction).map(|next| (next, direction));
+        [forward, Some((pos, direction.turn_left())), Some((pos, direction.turn_right()))]
+            .into_iter()
+            .flatten()
+            .filter_map(|next| Some((next, cost((pos, direction), next)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::grid::Topology;
+
+    const MAZE: &str = "\
+#######
+#S...E#
+#.###.#
+#.....#
+#######
+";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE.as_bytes()).unwrap()
+    }
+
+    fn open(_: char, to: char) -> Option<u64> {
+        (to != '#').then_some(1)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let search = grid.bfs(8, |_, to| to != '#');
+        assert_eq!(search.distance(12), Some(4));
+        assert_eq!(search.path_to(12), Some(vec![8, 9, 10, 11, 12]));
+        assert_eq!(search.distance(0), None);
+        assert_eq!(search.path_to(0), None);
+        // both ways around the block are 6 steps long
+        assert_eq!(search.distance(26), Some(6));
+        assert_eq!(search.predecessors(26), &[19, 25]);
+        assert_eq!(search.on_shortest_paths(26).len(), 12);
+        assert_eq!(search.path_counts()[&26], 2);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let grid = maze();
+        let search = grid.dijkstra(8, open);
+        assert_eq!(search.distance(12), Some(4));
+        assert_eq!(search.path_counts()[&26], 2);
+        // entering the end is expensive, so the bottom way to the corner is cheaper
+        let search = grid.dijkstra(8, |from, to| open(from, to).map(|c| if to == 'E' { c + 10 } else { c }));
+        assert_eq!(search.distance(12), Some(14));
+        assert_eq!(search.path_to(26), Some(vec![8, 15, 22, 23, 24, 25, 26]));
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = maze();
+        let search = grid.astar(8, 12, open).unwrap();
+        assert_eq!(search.distance(12), Some(4));
+        assert_eq!(search.path_to(12).unwrap().len(), 5);
+        assert!(grid.astar(8, 0, open).is_none());
+
+        // across the edge is shorter on a wrapping grid, the heuristic must not overestimate it
+        for topology in [Topology::WrapX, Topology::Torus] {
+            let ring = Grid::new(20, 1, '.').with_topology(topology);
+            assert_eq!(ring.astar(5, 18, open).unwrap().distance(18), Some(7));
+            assert_eq!(ring.bfs(5, |_, to| to != '#').distance(18), Some(7));
+        }
+    }
+
+    #[test]
+    fn test_facing() {
+        // reindeer style costs: a step costs 1, a quarter turn costs 1000
+        let grid = maze();
+        let cost = |(pos, _): (usize, Direction), (next, _): (usize, Direction)| match grid[next] {
+            '#' => None,
+            _ if pos == next => Some(1000),
+            _ => Some(1),
+        };
+        let (goal, search) = astar(
+            [(8, Direction::Right)],
+            |state| grid.facing_moves(state, cost),
+            |_| 0,
+            |(pos, _)| pos == 12,
+        )
+        .unwrap();
+        assert_eq!(goal, (12, Direction::Right));
+        assert_eq!(search.distance(goal), Some(4));
+
+        let search = dijkstra([(8, Direction::Down)], |state| grid.facing_moves(state, cost));
+        assert_eq!(search.distance((12, Direction::Right)), Some(1004));
+        assert_eq!(search.distance((26, Direction::Right)), Some(1006));
+    }
+}
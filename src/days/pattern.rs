@@ -0,0 +1,127 @@
+use crate::days::grid::Grid;
+use crate::days::transform::Transform;
+use crate::error::Result;
+
+/// Small grid to look for in a bigger one. `None` cells are wildcards that match anything.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern<T: Copy> {
+    cells: Grid<Option<T>>,
+}
+
+impl Pattern<char> {
+    /// A pattern drawn as text, e.g. `"M.S\n.A.\nM.S\n"` with `.` as the wildcard.
+    pub fn parse(text: &str, wildcard: char) -> Result<Pattern<char>> {
+        let chars = Grid::<char>::parse(text.as_bytes())?;
+        let mut cells = Grid::new(chars.width(), chars.num_rows(), None);
+        for (pos, &c) in chars.get_grid().iter().enumerate() {
+            cells[pos] = (c != wildcard).then_some(c);
+        }
+        Ok(Pattern { cells })
+    }
+}
+
+impl<T: Copy + PartialEq> Pattern<T> {
+    pub fn new(cells: Grid<Option<T>>) -> Pattern<T> {
+        Pattern { cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.cells.num_rows()
+    }
+
+    pub fn transformed(&self, transform: Transform) -> Pattern<T> {
+        Pattern { cells: self.cells.transformed(transform) }
+    }
+
+    /// The pattern under each of `transforms`, leaving out those that look like an earlier one
+    /// so that a symmetric pattern isn't found several times at the same spot.
+    pub fn variants(&self, transforms: &[Transform]) -> Vec<(Transform, Pattern<T>)> {
+        let mut variants: Vec<(Transform, Pattern<T>)> = Vec::new();
+        for &transform in transforms {
+            let pattern = self.transformed(transform);
+            if variants.iter().all(|(_, known)| *known != pattern) {
+                variants.push((transform, pattern));
+            }
+        }
+        variants
+    }
+
+    /// Offsets and values of the cells that aren't wildcards.
+    fn fixed_cells(&self) -> Vec<((usize, usize), T)> {
+        let cells = self.cells.get_grid().iter().enumerate();
+        cells.filter_map(|(pos, &v)| Some((self.cells.pos_to_coords(pos), v?))).collect()
+    }
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// Positions of the top left corner of every place where `pattern` matches.
+    pub fn find_pattern(&self, pattern: &Pattern<T>) -> Vec<usize> {
+        if pattern.width() > self.width() || pattern.num_rows() > self.num_rows() {
+            return Vec::new();
+        }
+        let fixed = pattern.fixed_cells();
+        let mut anchors = Vec::new();
+        for y in 0..=self.num_rows() - pattern.num_rows() {
+            for x in 0..=self.width() - pattern.width() {
+                if fixed.iter().all(|&((dx, dy), v)| self[(x + dx, y + dy)] == v) {
+                    anchors.push(self.coords_to_pos(x, y));
+                }
+            }
+        }
+        anchors
+    }
+
+    /// Like `find_pattern` with the pattern also turned by each of `transforms`, e.g.
+    /// `Transform::ROTATIONS` or `Transform::ALL`. Anchors are the top left corner of the
+    /// turned pattern. Transforms that give the same pattern only count once.
+    pub fn find_pattern_transformed(&self, pattern: &Pattern<T>, transforms: &[Transform]) -> Vec<(usize, Transform)> {
+        pattern
+            .variants(transforms)
+            .into_iter()
+            .flat_map(|(transform, variant)| self.find_pattern(&variant).into_iter().map(move |anchor| (anchor, transform)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "\
+#..#.
+.#...
+#.#..
+.....
+";
+
+    #[test]
+    fn test_find_pattern() {
+        let grid = Grid::<char>::parse(GRID.as_bytes()).unwrap();
+        let diagonal = Pattern::parse("#?\n?#\n", '?').unwrap();
+        assert_eq!(grid.find_pattern(&diagonal), vec![0, 6]);
+        let corner = Pattern::parse("#.\n.#\n#.\n", '?').unwrap();
+        assert_eq!(grid.find_pattern(&corner), vec![0]);
+        assert!(grid.find_pattern(&Pattern::parse("######\n", '?').unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_transformed() {
+        let grid = Grid::<char>::parse(GRID.as_bytes()).unwrap();
+        let diagonal = Pattern::parse("#?\n?#\n", '?').unwrap();
+        // a diagonal is its own transpose and half turn, leaving two distinct variants
+        assert_eq!(diagonal.variants(&Transform::ALL).len(), 2);
+        assert_eq!(
+            grid.find_pattern_transformed(&diagonal, &Transform::ALL),
+            vec![(0, Transform::Identity), (6, Transform::Identity), (5, Transform::Rotate90)]
+        );
+
+        let hook = Pattern::parse("##\n#?\n", '?').unwrap();
+        assert_eq!(hook.variants(&Transform::ROTATIONS).len(), 4);
+        assert_eq!(hook.variants(&Transform::ALL).len(), 4);
+        assert!(grid.find_pattern_transformed(&hook, &Transform::ALL).is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::days::grid::{Direction, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells only connect through their edges.
+    Four,
+    /// Cells also connect through their corners.
+    Eight,
+}
+
+/// A connected region of the grid, coordinates are `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Position of the region's first cell in reading order.
+    pub start: usize,
+    pub area: usize,
+    /// Cell edges that border another region or the outside of the grid.
+    pub perimeter: usize,
+    /// Corners of the outline, which is also its number of straight sides.
+    pub corners: usize,
+    pub min: (usize, usize),
+    pub max: (usize, usize),
+}
+
+impl Region {
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+
+    fn measure(labels: &Grid<usize>, label: usize, start: usize, cells: &[usize]) -> Region {
+        let inside = |pos: usize, direction: Direction| labels.step(pos, direction).is_some_and(|p| labels[p] == label);
+        let mut region = Region {
+            start,
+            area: cells.len(),
+            perimeter: 0,
+            corners: 0,
+            min: labels.pos_to_coords(start),
+            max: labels.pos_to_coords(start),
+        };
+        for &pos in cells {
+            let (x, y) = labels.pos_to_coords(pos);
+            region.min = (region.min.0.min(x), region.min.1.min(y));
+            region.max = (region.max.0.max(x), region.max.1.max(y));
+            for direction in Direction::CARDINAL {
+                region.perimeter += usize::from(!inside(pos, direction));
+                // the corner between `direction` and the next direction clockwise
+                let side = direction.turn_right();
+                let convex = !inside(pos, direction) && !inside(pos, side);
+                let concave = inside(pos, direction) && inside(pos, side) && !inside(pos, direction.turn_right_45());
+                region.corners += usize::from(convex || concave);
+            }
+        }
+        region
+    }
+}
+
+/// Every cell labelled with the index of its region in `regions`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Components {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T: Copy> Grid<T> {
+    /// Splits the grid into regions of connected cells, neighbors belong to the same region
+    /// when `same(a, b)` holds for their values. Uses an explicit stack instead of recursion.
+    /// On a wrapping grid regions join across the seam, and the seam is no edge for the
+    /// perimeter and corners either. `min` and `max` stay plain coordinates of the cells.
+    pub fn components(&self, connectivity: Connectivity, same: impl Fn(T, T) -> bool) -> Components {
+        let directions = match connectivity {
+            Connectivity::Four => &Direction::CARDINAL[..],
+            Connectivity::Eight => &Direction::ALL[..],
+        };
+        let mut labels = Grid::new(self.width(), self.num_rows(), usize::MAX).with_topology(self.topology());
+        let mut regions = vec![];
+        let mut stack = vec![];
+        for start in 0..self.len() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            let label = regions.len();
+            let mut cells = vec![];
+            labels[start] = label;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                cells.push(pos);
+                for next in directions.iter().filter_map(|&d| self.step(pos, d)) {
+                    if labels[next] == usize::MAX && same(self[pos], self[next]) {
+                        labels[next] = label;
+                        stack.push(next);
+                    }
+                }
+            }
+            regions.push(Region::measure(&labels, label, start, &cells));
+        }
+        Components { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::grid::Topology;
+
+    fn garden(text: &str) -> Grid<char> {
+        Grid::parse(text.as_bytes()).unwrap()
+    }
+
+    fn stats(components: &Components) -> Vec<(usize, usize, usize)> {
+        components.regions.iter().map(|r| (r.area, r.perimeter, r.sides())).collect()
+    }
+
+    #[test]
+    fn test_components() {
+        let grid = garden("AAAA\nBBCD\nBBCC\nEEEC\n");
+        let components = grid.components(Connectivity::Four, |a, b| a == b);
+        assert_eq!(stats(&components), vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]);
+        assert_eq!(components.labels.get_grid(), &vec![0, 0, 0, 0, 1, 1, 2, 3, 1, 1, 2, 2, 4, 4, 4, 2]);
+        let c = &components.regions[2];
+        assert_eq!((c.start, c.min, c.max), (6, (2, 1), (3, 3)));
+    }
+
+    #[test]
+    fn test_holes() {
+        let grid = garden("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n");
+        let components = grid.components(Connectivity::Four, |a, b| a == b);
+        assert_eq!(components.regions.len(), 5);
+        assert_eq!(stats(&components)[0], (21, 36, 20));
+    }
+
+    #[test]
+    fn test_eight_connectivity() {
+        let grid = garden("X.X\n.X.\nX.X\n");
+        assert_eq!(grid.components(Connectivity::Four, |a, b| a == b).regions.len(), 9);
+        let components = grid.components(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(components.regions.len(), 2);
+        assert_eq!(components.regions[0].area, 5);
+        assert_eq!(components.regions[1].area, 4);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = garden("A.A\n");
+        assert_eq!(stats(&grid.components(Connectivity::Four, |a, b| a == b))[0], (1, 4, 4));
+        // on a wrap-x grid both A touch across the seam, leaving a 2x1 box
+        let wrapped = grid.with_topology(Topology::WrapX);
+        let components = wrapped.components(Connectivity::Four, |a, b| a == b);
+        assert_eq!(components.regions.len(), 2);
+        assert_eq!(stats(&components)[0], (2, 6, 4));
+        assert_eq!(components.labels.topology(), Topology::WrapX);
+    }
+
+    #[test]
+    fn test_large_region() {
+        // a single 500x500 region would overflow the stack with a recursive fill
+        let grid = Grid::new(500, 500, '.');
+        let components = grid.components(Connectivity::Four, |a, b| a == b);
+        assert_eq!(stats(&components), vec![(250_000, 2000, 4)]);
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::days::grid::{Direction, Grid, Neighbors};
+
+/// `(x, y)` coordinates of a `SparseGrid`, rows grow downwards like in `Grid`.
+pub type Point = (i64, i64);
+
+/// Unbounded grid that only stores cells which were set, every other cell reads as `default`.
+/// The bounding box grows with every cell that is set and is what gets rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T: Copy> {
+    cells: HashMap<Point, T>,
+    default: T,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T: Copy> SparseGrid<T> {
+    pub fn new(default: T) -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new(), default, bounds: None }
+    }
+
+    pub fn get(&self, point: Point) -> T {
+        self.cells.get(&point).copied().unwrap_or(self.default)
+    }
+
+    /// Stores `value` at `point` and returns the previous value.
+    pub fn set(&mut self, point: Point, value: T) -> T {
+        let (x, y) = point;
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some(((min_x, min_y), (max_x, max_y))) => ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))),
+        });
+        self.cells.insert(point, value).unwrap_or(self.default)
+    }
+
+    /// Resets `point` to the default value, the bounding box keeps its size.
+    pub fn remove(&mut self, point: Point) -> T {
+        self.cells.remove(&point).unwrap_or(self.default)
+    }
+
+    /// Number of cells that were set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Cells that were set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, T)> + '_ {
+        self.cells.iter().map(|(&p, &v)| (p, v))
+    }
+
+    /// Smallest and largest coordinates of every cell that was ever set.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// `point` moved one step in `direction`, there is no edge to fall off.
+    pub fn step(&self, (x, y): Point, direction: Direction) -> Point {
+        let (dx, dy) = direction.offset();
+        (x + dx as i64, y + dy as i64)
+    }
+
+    /// The bounding box as text, one line per row, with `cell` turning values into characters.
+    pub fn render(&self, cell: impl Fn(T) -> char) -> String {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounds else {
+            return String::new();
+        };
+        let mut out = String::new();
+        for y in min_y..=max_y {
+            out.extend((min_x..=max_x).map(|x| cell(self.get((x, y)))));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T: Copy + Display> SparseGrid<T> {
+    /// Renders values that print as a single character, such as `char` or digits.
+    pub fn render_display(&self) -> String {
+        self.render(|v| v.to_string().chars().next().unwrap_or(' '))
+    }
+}
+
+impl<T: Copy> Neighbors for SparseGrid<T> {
+    type Pos = Point;
+    type Value = T;
+
+    fn offset_pos(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        Some((x + dx as i64, y + dy as i64))
+    }
+
+    fn value_at(&self, point: Point) -> T {
+        self.get(point)
+    }
+}
+
+impl<T: Copy + PartialEq> SparseGrid<T> {
+    /// Sparse copy of a dense grid, cells equal to `default` are left out.
+    pub fn from_grid(grid: &Grid<T>, default: T) -> SparseGrid<T> {
+        let mut sparse = SparseGrid::new(default);
+        for (pos, &value) in grid.get_grid().iter().enumerate().filter(|&(_, &v)| v != default) {
+            let (x, y) = grid.pos_to_coords(pos);
+            sparse.set((x as i64, y as i64), value);
+        }
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_bounds() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render_display(), "");
+        assert_eq!(grid.set((0, 0), '#'), '.');
+        assert_eq!(grid.set((-2, 1), '#'), '.');
+        assert_eq!(grid.set((0, 0), 'O'), '#');
+        assert_eq!(grid.get((5, 5)), '.');
+        assert_eq!(grid.bounds(), Some(((-2, 0), (0, 1))));
+        assert_eq!(grid.render_display(), "..O\n#..\n");
+        assert_eq!(grid.remove((0, 0)), 'O');
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid.render(|c| if c == '#' { '@' } else { ' ' }), "   \n@  \n");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut grid = SparseGrid::new(0);
+        grid.set((0, -1), 7);
+        assert_eq!(grid.step((0, 0), Direction::Up), (0, -1));
+        assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<_>>(), vec![((0, -1), 7), ((1, 0), 0), ((0, 1), 0), ((-1, 0), 0)]);
+        assert_eq!(grid.neighbors8((100, 100)).count(), 8);
+        assert_eq!(grid.neighbors_within((0, 0), 2, crate::days::grid::Metric::Manhattan).count(), 12);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let dense = Grid::<char>::parse("..#\n#..\n".as_bytes()).unwrap();
+        let sparse = SparseGrid::from_grid(&dense, '.');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.get((2, 0)), '#');
+        assert_eq!(sparse.render_display(), "..#\n#..\n");
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::{Display, Write as _};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::days::grid::Grid;
+use crate::days::image::{Overlay, Rgb};
+
+const RESET: &str = "\x1b[0m";
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Foreground and background of a cell, `None` keeps the terminal's own color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Style {
+    fg: Option<Rgb>,
+    bg: Option<Rgb>,
+}
+
+impl Style {
+    fn escape(self) -> String {
+        let mut out = RESET.to_string();
+        if let Some(Rgb(r, g, b)) = self.fg {
+            let _ = write!(out, "\x1b[38;2;{};{};{}m", r, g, b);
+        }
+        if let Some(Rgb(r, g, b)) = self.bg {
+            let _ = write!(out, "\x1b[48;2;{};{};{}m", r, g, b);
+        }
+        out
+    }
+}
+
+impl<T: Copy + Display> Grid<T> {
+    /// The grid like its `Display`, with 24-bit ANSI colors: `palette` picks the text color
+    /// of a value and the highlighted positions get their overlay's color as background.
+    /// Escape codes are only written where the style changes.
+    pub fn render_ansi(&self, palette: impl Fn(T) -> Option<Rgb>, highlights: &[Overlay]) -> String {
+        let mut backgrounds = vec![None; self.len()];
+        for highlight in highlights {
+            for &pos in highlight.positions().iter().filter(|&&pos| pos < self.len()) {
+                backgrounds[pos] = Some(highlight.color());
+            }
+        }
+
+        let mut out = String::new();
+        for y in 0..self.num_rows() {
+            let mut current = Style::default();
+            for x in 0..self.width() {
+                let pos = self.coords_to_pos(x, y);
+                let style = Style { fg: palette(self[pos]), bg: backgrounds[pos] };
+                if style != current {
+                    out.push_str(&style.escape());
+                    current = style;
+                }
+                let _ = write!(out, "{}", self[pos]);
+            }
+            if current != Style::default() {
+                out.push_str(RESET);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Writes frames one after another. On a terminal each frame replaces the previous one and
+/// frames are paced to the frame rate, a recording writes them all to a file with a
+/// `-- frame N --` line in front of each and doesn't wait.
+pub struct Animation<W: Write> {
+    out: W,
+    /// Time between frames, `None` when recording.
+    delay: Option<Duration>,
+    last_frame: Option<Instant>,
+    frames: usize,
+}
+
+impl Animation<io::Stdout> {
+    /// Plays on stdout at `fps` frames per second, 0 or less plays as fast as possible.
+    pub fn terminal(fps: f64) -> Animation<io::Stdout> {
+        Animation { out: io::stdout(), delay: Some(frame_delay(fps)), last_frame: None, frames: 0 }
+    }
+}
+
+fn frame_delay(fps: f64) -> Duration {
+    // `fps > 0.0` is false for NaN too
+    if fps > 0.0 {
+        Duration::try_from_secs_f64(1.0 / fps).unwrap_or(Duration::MAX)
+    } else {
+        Duration::ZERO
+    }
+}
+
+impl Animation<BufWriter<File>> {
+    pub fn record(path: impl AsRef<Path>) -> io::Result<Animation<BufWriter<File>>> {
+        Ok(Animation::recording(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> Animation<W> {
+    /// Records into any writer, e.g. a `Vec<u8>` in tests.
+    pub fn recording(out: W) -> Animation<W> {
+        Animation { out, delay: None, last_frame: None, frames: 0 }
+    }
+
+    /// Number of frames written so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn frame(&mut self, text: &str) -> io::Result<()> {
+        match self.delay {
+            Some(delay) => {
+                if let Some(elapsed) = self.last_frame.map(|t| t.elapsed()) {
+                    thread::sleep(delay.saturating_sub(elapsed));
+                }
+                write!(self.out, "{}{}", CLEAR, text)?;
+                self.last_frame = Some(Instant::now());
+            }
+            None => write!(self.out, "-- frame {} --\n{}", self.frames, text)?,
+        }
+        self.frames += 1;
+        self.out.flush()
+    }
+
+    /// One frame per grid state.
+    pub fn play_states<'g, T: Copy + Display + 'g>(
+        &mut self,
+        states: impl IntoIterator<Item = &'g Grid<T>>,
+        palette: impl Fn(T) -> Option<Rgb>,
+    ) -> io::Result<()> {
+        for grid in states {
+            self.frame(&grid.render_ansi(&palette, &[]))?;
+        }
+        Ok(())
+    }
+
+    /// One frame per position on a fixed grid, the current position is highlighted with `head`
+    /// and the ones before it with `trail`.
+    pub fn play_positions<T: Copy + Display>(
+        &mut self,
+        grid: &Grid<T>,
+        positions: impl IntoIterator<Item = usize>,
+        palette: impl Fn(T) -> Option<Rgb>,
+        trail: Rgb,
+        head: Rgb,
+    ) -> io::Result<()> {
+        let mut visited = Vec::new();
+        for pos in positions {
+            let highlights = [Overlay::new(visited.iter().copied(), trail), Overlay::new([pos], head)];
+            self.frame(&grid.render_ansi(&palette, &highlights))?;
+            visited.push(pos);
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("#..\n...\n".as_bytes()).unwrap()
+    }
+
+    fn walls(c: char) -> Option<Rgb> {
+        (c == '#').then_some(Rgb::RED)
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let grid = grid();
+        assert_eq!(grid.render_ansi(|_| None, &[]), "#..\n...\n");
+        let text = grid.render_ansi(walls, &[Overlay::new([4], Rgb::BLUE)]);
+        assert_eq!(
+            text,
+            "\x1b[0m\x1b[38;2;255;0;0m#\x1b[0m..\n.\x1b[0m\x1b[48;2;0;0;255m.\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let grid = grid();
+        let mut animation = Animation::recording(Vec::new());
+        animation.play_positions(&grid, [1, 2], |_| None, Rgb::GRAY, Rgb::YELLOW).unwrap();
+        let mut moved = grid.clone();
+        moved.swap(0, 5);
+        animation.play_states([&grid, &moved], |_| None).unwrap();
+        assert_eq!(animation.frames(), 4);
+
+        let recorded = String::from_utf8(animation.into_inner()).unwrap();
+        let frames = recorded.split("-- frame ").skip(1).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 4);
+        assert!(frames[0].starts_with("0 --\n#\x1b[0m\x1b[48;2;255;255;0m.\x1b[0m.\n"));
+        assert!(frames[1].contains("\x1b[48;2;128;128;128m.\x1b[0m\x1b[48;2;255;255;0m.\x1b[0m\n"));
+        assert_eq!(frames[3], "3 --\n...\n..#\n");
+    }
+
+    #[test]
+    fn test_frame_delay() {
+        assert_eq!(frame_delay(4.0), Duration::from_millis(250));
+        assert_eq!(frame_delay(0.0), Duration::ZERO);
+        assert_eq!(frame_delay(-1.0), Duration::ZERO);
+        assert_eq!(frame_delay(f64::NAN), Duration::ZERO);
+        assert_eq!(frame_delay(f64::INFINITY), Duration::ZERO);
+        assert_eq!(frame_delay(1e-320), Duration::MAX);
+    }
+
+    #[test]
+    fn test_record_file() {
+        let path = std::env::temp_dir().join(format!("aoc-animation-{}.txt", std::process::id()));
+        let mut animation = Animation::record(&path).unwrap();
+        animation.frame("ab\n").unwrap();
+        drop(animation);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "-- frame 0 --\nab\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,283 @@
+use std::iter;
+
+use crate::days::grid::{Direction, Grid};
+
+/// One of the eight ways to rotate or mirror a grid. Rotations are clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirrors left and right.
+    FlipH,
+    /// Mirrors top and bottom.
+    FlipV,
+    /// Mirrors along the main diagonal, `(x, y)` becomes `(y, x)`.
+    Transpose,
+    /// Mirrors along the anti-diagonal.
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipH,
+        Transform::FlipV,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    pub const ROTATIONS: [Transform; 4] = [Transform::Identity, Transform::Rotate90, Transform::Rotate180, Transform::Rotate270];
+
+    /// Width and height of the result of transforming a `width` x `height` grid.
+    pub fn size(self, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Transform::Rotate90 | Transform::Rotate270 | Transform::Transpose | Transform::AntiTranspose => (height, width),
+            _ => (width, height),
+        }
+    }
+
+    pub fn inverse(self) -> Transform {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            t => t,
+        }
+    }
+
+    /// Coordinates in a `width` x `height` source grid of `(x, y)` in the transformed grid.
+    pub fn source_coords(self, (x, y): (usize, usize), width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Transform::Identity => (x, y),
+            Transform::Rotate90 => (y, height - 1 - x),
+            Transform::Rotate180 => (width - 1 - x, height - 1 - y),
+            Transform::Rotate270 => (width - 1 - y, x),
+            Transform::FlipH => (width - 1 - x, y),
+            Transform::FlipV => (x, height - 1 - y),
+            Transform::Transpose => (y, x),
+            Transform::AntiTranspose => (width - 1 - y, height - 1 - x),
+        }
+    }
+
+    /// Coordinates in the transformed grid of `(x, y)` in a `width` x `height` source grid.
+    pub fn target_coords(self, coords: (usize, usize), width: usize, height: usize) -> (usize, usize) {
+        let (w, h) = self.size(width, height);
+        self.inverse().source_coords(coords, w, h)
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    /// Positions and values from `pos` in `direction` up to the edge of the grid, `pos` included.
+    /// Lines end at the edge on wrapping grids too, so they never go around forever.
+    pub fn line_from(&self, pos: usize, direction: Direction) -> impl Iterator<Item = (usize, T)> + '_ {
+        iter::successors(Some(pos), move |&p| self.step_within(p, direction)).map(|p| (p, self[p]))
+    }
+
+    /// Each row from left to right, top row first.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (usize, T)> + '_> + '_ {
+        (0..self.num_rows()).map(|y| self.line_from(self.coords_to_pos(0, y), Direction::Right))
+    }
+
+    /// Each column from top to bottom, leftmost column first.
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = (usize, T)> + '_> + '_ {
+        (0..self.width()).map(|x| self.line_from(x, Direction::Down))
+    }
+
+    /// Each diagonal running down and to the right, starting with the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (usize, T)> + '_> + '_ {
+        let left = (1..self.num_rows()).rev().map(|y| self.coords_to_pos(0, y));
+        left.chain(0..self.width()).map(|start| self.line_from(start, Direction::DownRight))
+    }
+
+    /// Each anti-diagonal running down and to the left, starting with the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (usize, T)> + '_> + '_ {
+        let right = (1..self.num_rows()).map(|y| self.coords_to_pos(self.width() - 1, y));
+        (0..self.width()).chain(right).map(|start| self.line_from(start, Direction::DownLeft))
+    }
+
+    /// A copy of the grid turned by `transform`, map positions back with `Transform::source_coords`.
+    pub fn transformed(&self, transform: Transform) -> Grid<T> {
+        let (width, height) = transform.size(self.width(), self.num_rows());
+        // turning the grid by a quarter also turns which axes wrap around
+        let topology = if transform.size(2, 1) == (1, 2) { self.topology().transposed() } else { self.topology() };
+        let mut grid = Grid::new(width, height, self[0]).with_topology(topology);
+        for y in 0..height {
+            for x in 0..width {
+                grid[(x, y)] = self[transform.source_coords((x, y), self.width(), self.num_rows())];
+            }
+        }
+        grid
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        self.transformed(Transform::Transpose)
+    }
+
+    pub fn rotate90(&self) -> Grid<T> {
+        self.transformed(Transform::Rotate90)
+    }
+
+    pub fn rotate180(&self) -> Grid<T> {
+        self.transformed(Transform::Rotate180)
+    }
+
+    pub fn rotate270(&self) -> Grid<T> {
+        self.transformed(Transform::Rotate270)
+    }
+
+    pub fn flip_h(&self) -> Grid<T> {
+        self.transformed(Transform::FlipH)
+    }
+
+    pub fn flip_v(&self) -> Grid<T> {
+        self.transformed(Transform::FlipV)
+    }
+
+    /// A borrowed `width` x `height` view with its top left corner at `(x, y)`,
+    /// `None` when it doesn't fit inside the grid.
+    pub fn window(&self, x: usize, y: usize, width: usize, height: usize) -> Option<Window<'_, T>> {
+        let fits = x + width <= self.width() && y + height <= self.num_rows();
+        fits.then_some(Window { grid: self, x, y, width, height })
+    }
+}
+
+/// Rectangular part of a grid, coordinates are relative to its top left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct Window<'a, T: Copy> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Copy> Window<'_, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.height
+    }
+
+    /// Coordinates in the underlying grid of `(x, y)` in the window.
+    pub fn source_coords(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        (self.x + x, self.y + y)
+    }
+
+    /// Position in the underlying grid of `(x, y)` in the window.
+    pub fn source_pos(&self, coords: (usize, usize)) -> usize {
+        let (x, y) = self.source_coords(coords);
+        self.grid.coords_to_pos(x, y)
+    }
+
+    /// Coordinates in the window of a position of the underlying grid, `None` outside the window.
+    pub fn window_coords(&self, pos: usize) -> Option<(usize, usize)> {
+        let (x, y) = self.grid.pos_to_coords(pos);
+        let inside = (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y);
+        inside.then(|| (x - self.x, y - self.y))
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<T> {
+        (x < self.width && y < self.height).then(|| self.grid[self.source_coords((x, y))])
+    }
+
+    /// Each row of the window with positions in the underlying grid.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (usize, T)> + '_> + '_ {
+        (0..self.height).map(move |y| (0..self.width).map(move |x| (self.source_pos((x, y)), self.grid[self.source_coords((x, y))])))
+    }
+
+    pub fn to_grid(self) -> Grid<T> {
+        let mut grid = Grid::new(self.width, self.height, self.grid[self.source_coords((0, 0))]);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                grid[(x, y)] = self.grid[self.source_coords((x, y))];
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::grid::Topology;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n".as_bytes()).unwrap()
+    }
+
+    fn text(lines: impl Iterator<Item = impl Iterator<Item = (usize, char)>>) -> Vec<String> {
+        lines.map(|line| line.map(|(_, c)| c).collect()).collect()
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = grid();
+        assert_eq!(text(grid.rows()), vec!["abc", "def"]);
+        assert_eq!(text(grid.cols()), vec!["ad", "be", "cf"]);
+        assert_eq!(text(grid.diagonals()), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(text(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+        assert_eq!(grid.line_from(1, Direction::DownRight).collect::<Vec<_>>(), vec![(1, 'b'), (5, 'f')]);
+    }
+
+    #[test]
+    fn test_lines_on_torus() {
+        let grid = grid().with_topology(Topology::Torus);
+        assert_eq!(grid.rows().next().unwrap().take(100).count(), 3);
+        assert_eq!(text(grid.rows()), vec!["abc", "def"]);
+        assert_eq!(text(grid.cols()), vec!["ad", "be", "cf"]);
+        assert_eq!(text(grid.diagonals()), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(text(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+        assert_eq!(grid.line_from(2, Direction::Left).count(), 3);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n\n");
+        assert_eq!(grid.rotate90().to_string(), "da\neb\nfc\n\n");
+        assert_eq!(grid.rotate180().to_string(), "fed\ncba\n\n");
+        assert_eq!(grid.rotate270().to_string(), "cf\nbe\nad\n\n");
+        assert_eq!(grid.flip_h().to_string(), "cba\nfed\n\n");
+        assert_eq!(grid.flip_v().to_string(), "def\nabc\n\n");
+        assert_eq!(grid.transformed(Transform::AntiTranspose).to_string(), "fc\neb\nda\n\n");
+        assert_eq!(grid.rotate90().rotate90(), grid.rotate180());
+        let wrapping = grid.with_topology(Topology::WrapX);
+        assert_eq!(wrapping.rotate90().topology(), Topology::WrapY);
+        assert_eq!(wrapping.flip_h().topology(), Topology::WrapX);
+    }
+
+    #[test]
+    fn test_mapping() {
+        let grid = grid();
+        for transform in Transform::ALL {
+            let turned = grid.transformed(transform);
+            for pos in 0..grid.len() {
+                let coords = grid.pos_to_coords(pos);
+                let mapped = transform.target_coords(coords, grid.width(), grid.num_rows());
+                assert_eq!(turned[mapped], grid[pos]);
+                assert_eq!(transform.source_coords(mapped, grid.width(), grid.num_rows()), coords);
+            }
+        }
+    }
+
+    #[test]
+    fn test_window() {
+        let grid = Grid::<char>::parse("abcd\nefgh\nijkl\n".as_bytes()).unwrap();
+        let window = grid.window(1, 1, 2, 2).unwrap();
+        assert_eq!(window.to_grid().to_string(), "fg\njk\n\n");
+        assert_eq!(window.get((1, 0)), Some('g'));
+        assert_eq!(window.get((2, 0)), None);
+        assert_eq!(window.source_coords((1, 1)), (2, 2));
+        assert_eq!(window.source_pos((1, 1)), 10);
+        assert_eq!(window.window_coords(10), Some((1, 1)));
+        assert_eq!(window.window_coords(0), None);
+        assert_eq!(text(window.rows()), vec!["fg", "jk"]);
+        assert!(grid.window(3, 0, 2, 1).is_none());
+    }
+}
@@ -0,0 +1,112 @@
+use std::any::Any;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+/// Error for everything that can go wrong between reading an input and producing answers.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// Malformed input, `line` and `column` are 1-based.
+    Parse { line: usize, column: usize, message: String },
+    /// Input that parsed but can't be solved, e.g. a map without a guard.
+    Invalid(String),
+    /// The day panicked, usually on input it didn't expect.
+    Panic(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> Error {
+        Error::Parse { line, column, message: message.into() }
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Error {
+        Error::Invalid(message.into())
+    }
+
+    /// Turns the payload of a caught panic into an error carrying the panic message.
+    pub fn from_panic(payload: Box<dyn Any + Send>) -> Error {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast_ref::<&str>().map_or("unknown cause", |m| m).to_string(),
+        };
+        Error::Panic(message)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "could not read input: {}", e),
+            Error::Parse { line, column, message } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::Invalid(message) => write!(f, "invalid input: {}", message),
+            Error::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+/// Lines of the reader together with their 1-based line number.
+pub fn lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<(usize, String)>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| line.map(|l| (idx + 1, l)).map_err(Error::from))
+}
+
+/// 1-based column of `field`, which must be a slice of `line`.
+pub fn column_of(line: &str, field: &str) -> usize {
+    let offset = (field.as_ptr() as usize).saturating_sub(line.as_ptr() as usize).min(line.len());
+    line[..offset].chars().count() + 1
+}
+
+/// Parses `field`, a slice of line number `line_no`, and reports where it failed.
+pub fn parse_at<T: FromStr>(line_no: usize, line: &str, field: &str) -> Result<T>
+where
+    T::Err: fmt::Display,
+{
+    field
+        .parse::<T>()
+        .map_err(|e| Error::at(line_no, column_of(line, field), format!("invalid value `{}`: {}", field, e)))
+}
+
+/// Parses a single digit at the 0-based character index `idx` of the line.
+pub fn digit_at(line_no: usize, idx: usize, c: char) -> Result<u32> {
+    c.to_digit(10)
+        .ok_or_else(|| Error::at(line_no, idx + 1, format!("expected a digit, found `{}`", c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_at() {
+        let line = "12 x4 7";
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        assert_eq!(parse_at::<u32>(3, line, fields[0]).unwrap(), 12);
+        let err = parse_at::<u32>(3, line, fields[1]).unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 4: invalid value `x4`: invalid digit found in string");
+    }
+
+    #[test]
+    fn test_lines() {
+        let numbered = lines("a\nb\n".as_bytes()).collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(numbered, vec![(1, "a".to_string()), (2, "b".to_string())]);
+    }
+
+    #[test]
+    fn test_digit_at() {
+        assert_eq!(digit_at(1, 0, '7').unwrap(), 7);
+        assert_eq!(digit_at(2, 4, '.').unwrap_err().to_string(), "line 2, column 5: expected a digit, found `.`");
+    }
+}
@@ -1,37 +1,142 @@
+use std::path::Path;
+use std::process::ExitCode;
 use std::time::Instant;
 
+mod answer;
+mod bench;
+mod cli;
 mod days;
+mod error;
+mod report;
+mod runner;
+mod scaffold;
+mod solution;
+mod verify;
 
-use days::*;
-
-fn main() {
-    let days = [
-        day01::solve,
-        day02::solve,
-        day03::solve,
-        day04::solve,
-        day05::solve,
-        day06::solve,
-        day07::solve,
-        day08::solve,
-        day09::solve,
-        day10::solve,
-        day11::solve,
-    ];
-    println!(
-        "{0: <3} | {1: <10} | {2: <14} | {3: <10}",
-        "Day", "Part 1", "Part 2", "μs"
-    );
-    for (idx, day) in days.iter().enumerate() {
-        let now = Instant::now();
-        let (p1, p2) = day();
-        let micros = now.elapsed().as_micros();
-        println!(
-            "{0:<3} | {1:<10} | {2:<14} | {3:<10}",
-            format! {"{:02}", idx+1},
-            p1,
-            p2,
-            micros
-        )
+use bench::{BenchOptions, Baseline};
+use cli::{Command, Options};
+use verify::Expected;
+
+/// Runs the benchmark and returns false when it failed or found a regression.
+fn run_bench(days: &[(&solution::Day, Vec<u8>)], options: &Options, bench_options: &BenchOptions) -> bool {
+    let baseline = match &bench_options.baseline {
+        Some(path) => match Baseline::load(path) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("could not load baseline {}: {}", path.display(), e);
+                return false;
+            }
+        },
+        None => None,
+    };
+    bench::print_header();
+    let mut regressed = false;
+    let mut failed = false;
+    let mut benches = vec![];
+    for (day, input) in days {
+        match bench::bench_day(day, input, options.part, bench_options) {
+            Ok(result) => {
+                regressed |= bench::print_day(&result, baseline.as_ref(), bench_options.threshold);
+                benches.push(result);
+            }
+            Err(e) => {
+                eprintln!("day {:02}: {}", day.day, e);
+                failed = true;
+            }
+        }
+    }
+    if let Some(path) = &bench_options.save_baseline {
+        if let Err(e) = bench::to_baseline(&benches).save(path) {
+            eprintln!("could not save baseline {}: {}", path.display(), e);
+            return false;
+        }
+    }
+    if regressed {
+        eprintln!("median slower than the baseline by more than {}%", bench_options.threshold);
+    }
+    !regressed && !failed
+}
+
+fn main() -> ExitCode {
+    let registry = days::registry();
+    let available = registry.iter().map(|d| d.day).collect::<Vec<_>>();
+    let options = match Command::parse(std::env::args().skip(1), &available) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::NewDay { day, title }) => {
+            return match scaffold::new_day(Path::new("."), day, &title) {
+                Ok(written) => {
+                    written.iter().for_each(|path| println!("wrote {}", path.display()));
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    let mut days = vec![];
+    for day in registry.iter().filter(|d| options.days.contains(&d.day)) {
+        let source = options.input_for(day.day);
+        match source.read() {
+            Ok(input) => days.push((day, input)),
+            Err(e) => {
+                eprintln!("day {:02}: could not read {}: {}", day.day, source, e);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if let Some(bench_options) = &options.bench {
+        if !run_bench(&days, &options, bench_options) {
+            status = ExitCode::FAILURE;
+        }
+        return status;
+    }
+
+    let wall_clock = Instant::now();
+    let outcomes = if options.parallel {
+        runner::run_days_parallel(&days, options.part)
+    } else {
+        days.iter().map(|(day, input)| runner::run_day(day, input, options.part)).collect()
+    };
+    let wall_clock = wall_clock.elapsed();
+
+    let mut results = vec![];
+    for ((day, _), outcome) in days.iter().zip(outcomes) {
+        match outcome {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                eprintln!("day {:02}: {}", day.day, e);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    if options.verify {
+        for result in results.iter_mut() {
+            match Expected::load(&options.input_for(result.day)) {
+                Ok(expected) => {
+                    if !result.verify(&expected) {
+                        status = ExitCode::FAILURE;
+                    }
+                }
+                Err(e) => {
+                    eprintln!("day {:02}: could not load answers: {}", result.day, e);
+                    status = ExitCode::FAILURE;
+                }
+            }
+        }
     }
+    print!("{}", report::render(&results, options.format, options.parallel.then_some(wall_clock)));
+    status
 }
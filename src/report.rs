@@ -0,0 +1,347 @@
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::runner::DayResult;
+use crate::verify::Verdict;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("unknown format `{}`, expected text, json, csv or markdown", s)),
+        }
+    }
+}
+
+enum Cell {
+    Day,
+    Text(fn(&DayResult) -> Option<String>),
+    Time(fn(&DayResult) -> Duration),
+}
+
+struct Column {
+    header: &'static str,
+    /// Field name in csv and json output.
+    key: &'static str,
+    cell: Cell,
+}
+
+fn answer(a: &Option<Answer>) -> Option<String> {
+    a.as_ref().map(|a| a.to_string())
+}
+
+fn verdict(v: &Option<Verdict>) -> Option<String> {
+    v.as_ref().map(|v| v.to_string())
+}
+
+/// Columns of the results table, the check columns are only shown when answers were verified.
+fn columns(results: &[DayResult]) -> Vec<Column> {
+    let mut columns = vec![
+        Column { header: "Day", key: "day", cell: Cell::Day },
+        Column { header: "Title", key: "title", cell: Cell::Text(|r| Some(r.title.to_string())) },
+        Column { header: "Part 1", key: "part1", cell: Cell::Text(|r| answer(&r.part1)) },
+        Column { header: "Part 2", key: "part2", cell: Cell::Text(|r| answer(&r.part2)) },
+    ];
+    if results.iter().any(|r| r.part1_verdict.is_some() || r.part2_verdict.is_some()) {
+        columns.push(Column { header: "P1 check", key: "part1_check", cell: Cell::Text(|r| verdict(&r.part1_verdict)) });
+        columns.push(Column { header: "P2 check", key: "part2_check", cell: Cell::Text(|r| verdict(&r.part2_verdict)) });
+    }
+    columns.extend([
+        Column { header: "Parse μs", key: "parse_us", cell: Cell::Time(|r| r.parse_time) },
+        Column { header: "P1 μs", key: "part1_us", cell: Cell::Time(|r| r.part1_time) },
+        Column { header: "P2 μs", key: "part2_us", cell: Cell::Time(|r| r.part2_time) },
+        Column { header: "Total μs", key: "total_us", cell: Cell::Time(DayResult::total_time) },
+    ]);
+    columns
+}
+
+fn micros(d: Duration) -> u128 {
+    d.as_micros()
+}
+
+impl Column {
+    fn is_numeric(&self) -> bool {
+        matches!(self.cell, Cell::Time(_))
+    }
+
+    /// Cell shown in the tables, days are zero padded.
+    fn cell(&self, result: &DayResult) -> String {
+        match self.cell {
+            Cell::Day => format!("{:02}", result.day),
+            Cell::Text(text) => text(result).unwrap_or_default(),
+            Cell::Time(time) => micros(time(result)).to_string(),
+        }
+    }
+
+    /// Cell of the total row, timings are summed over all days.
+    fn total(&self, results: &[DayResult]) -> String {
+        match self.cell {
+            Cell::Time(time) => micros(results.iter().map(time).sum()).to_string(),
+            _ if self.key == "title" => "Total".to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Table cells for every day followed by the total row.
+fn rows(columns: &[Column], results: &[DayResult]) -> Vec<Vec<String>> {
+    let mut rows = results
+        .iter()
+        .map(|r| columns.iter().map(|c| c.cell(r)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    rows.push(columns.iter().map(|c| c.total(results)).collect());
+    rows
+}
+
+/// Renders the results, `wall_clock` is the elapsed time of a parallel run which is shown
+/// next to the summed day times. CSV only has the per-day rows and leaves it out.
+pub fn render(results: &[DayResult], format: Format, wall_clock: Option<Duration>) -> String {
+    let columns = columns(results);
+    let sum = results.iter().map(DayResult::total_time).sum::<Duration>();
+    let summary = wall_clock.map(|wall| {
+        format!(
+            "Wall clock {} μs, sum of days {} μs ({:.1}x)\n",
+            micros(wall),
+            micros(sum),
+            sum.as_secs_f64() / wall.as_secs_f64().max(f64::MIN_POSITIVE)
+        )
+    });
+    match format {
+        Format::Text => text(&columns, results) + &summary.unwrap_or_default(),
+        Format::Json => json(&columns, results, wall_clock),
+        Format::Csv => csv(&columns, results),
+        Format::Markdown => markdown(&columns, results) + &summary.map(|s| "\n".to_string() + &s).unwrap_or_default(),
+    }
+}
+
+/// Fixed width table where every column is as wide as its widest cell.
+fn text(columns: &[Column], results: &[DayResult]) -> String {
+    let rows = rows(columns, results);
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(col, c)| rows.iter().map(|r| r[col].chars().count()).chain([c.header.chars().count()]).max().unwrap())
+        .collect::<Vec<_>>();
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(columns)
+            .enumerate()
+            .map(|(col, (cell, column))| match column.is_numeric() {
+                true => format!("{:>width$}", cell, width = widths[col]),
+                false => format!("{:<width$}", cell, width = widths[col]),
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+    let mut out = line(columns.iter().map(|c| c.header).collect()) + "\n";
+    for row in &rows {
+        out += &line(row.iter().map(|c| c.as_str()).collect());
+        out += "\n";
+    }
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out + "\""
+}
+
+/// Answers are strings so large integers survive JSON parsers that use doubles.
+fn json(columns: &[Column], results: &[DayResult], wall_clock: Option<Duration>) -> String {
+    let days = results
+        .iter()
+        .map(|r| {
+            let fields = columns.iter().map(|c| {
+                let value = match c.cell {
+                    Cell::Day => r.day.to_string(),
+                    Cell::Text(text) => text(r).map(|t| json_string(&t)).unwrap_or("null".to_string()),
+                    Cell::Time(time) => micros(time(r)).to_string(),
+                };
+                format!("\"{}\": {}", c.key, value)
+            });
+            format!("    {{{}}}", fields.collect::<Vec<_>>().join(", "))
+        })
+        .collect::<Vec<_>>();
+    let wall_clock = wall_clock.map(|wall| format!(",\n  \"wall_clock_us\": {}", micros(wall)));
+    format!(
+        "{{\n  \"days\": [\n{}\n  ],\n  \"total_us\": {}{}\n}}\n",
+        days.join(",\n"),
+        micros(results.iter().map(DayResult::total_time).sum()),
+        wall_clock.unwrap_or_default()
+    )
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv(columns: &[Column], results: &[DayResult]) -> String {
+    let mut out = columns.iter().map(|c| c.key).collect::<Vec<_>>().join(",") + "\n";
+    for r in results {
+        let fields = columns.iter().map(|c| match c.cell {
+            Cell::Day => r.day.to_string(),
+            _ => csv_field(&c.cell(r)),
+        });
+        out += &fields.collect::<Vec<_>>().join(",");
+        out += "\n";
+    }
+    out
+}
+
+fn markdown(columns: &[Column], results: &[DayResult]) -> String {
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let mut out = line(columns.iter().map(|c| c.header.to_string()).collect());
+    out += &line(columns.iter().map(|c| if c.is_numeric() { "---:" } else { "---" }.to_string()).collect());
+    let rows = rows(columns, results);
+    let last = rows.len() - 1;
+    for (idx, row) in rows.into_iter().enumerate() {
+        let cells = row.into_iter().map(|cell| match (idx == last, cell.is_empty()) {
+            (_, true) => cell,
+            (true, false) => format!("**{}**", cell),
+            (false, false) => cell.replace('|', "\\|"),
+        });
+        out += &line(cells.collect());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<DayResult> {
+        vec![
+            DayResult {
+                day: 1,
+                title: "Historian Hysteria",
+                part1: Some(11.into()),
+                part2: Some(31.into()),
+                parse_time: Duration::from_micros(20),
+                part1_time: Duration::from_micros(3),
+                part2_time: Duration::from_micros(5),
+                part1_verdict: None,
+                part2_verdict: None,
+            },
+            DayResult {
+                day: 7,
+                title: "Bridge \"Repair\"",
+                part1: None,
+                part2: Some("1,2|3".into()),
+                parse_time: Duration::from_micros(100),
+                part1_time: Duration::ZERO,
+                part2_time: Duration::from_micros(1234567),
+                part1_verdict: None,
+                part2_verdict: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_text() {
+        let expected = "\
+Day | Title              | Part 1 | Part 2 | Parse μs | P1 μs |   P2 μs | Total μs
+01  | Historian Hysteria | 11     | 31     |       20 |     3 |       5 |       28
+07  | Bridge \"Repair\"    |        | 1,2|3  |      100 |     0 | 1234567 |  1234667
+    | Total              |        |        |      120 |     3 | 1234572 |  1234695
+";
+        assert_eq!(render(&results(), Format::Text, None), expected);
+    }
+
+    #[test]
+    fn test_json() {
+        let expected = r#"{
+  "days": [
+    {"day": 1, "title": "Historian Hysteria", "part1": "11", "part2": "31", "parse_us": 20, "part1_us": 3, "part2_us": 5, "total_us": 28},
+    {"day": 7, "title": "Bridge \"Repair\"", "part1": null, "part2": "1,2|3", "parse_us": 100, "part1_us": 0, "part2_us": 1234567, "total_us": 1234667}
+  ],
+  "total_us": 1234695
+}
+"#;
+        assert_eq!(render(&results(), Format::Json, None), expected);
+    }
+
+    #[test]
+    fn test_csv() {
+        let expected = "\
+day,title,part1,part2,parse_us,part1_us,part2_us,total_us
+1,Historian Hysteria,11,31,20,3,5,28
+7,\"Bridge \"\"Repair\"\"\",,\"1,2|3\",100,0,1234567,1234667
+";
+        assert_eq!(render(&results(), Format::Csv, None), expected);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let expected = "\
+| Day | Title | Part 1 | Part 2 | Parse μs | P1 μs | P2 μs | Total μs |
+| --- | --- | --- | --- | ---: | ---: | ---: | ---: |
+| 01 | Historian Hysteria | 11 | 31 | 20 | 3 | 5 | 28 |
+| 07 | Bridge \"Repair\" |  | 1,2\\|3 | 100 | 0 | 1234567 | 1234667 |
+|  | **Total** |  |  | **120** | **3** | **1234572** | **1234695** |
+";
+        assert_eq!(render(&results(), Format::Markdown, None), expected);
+    }
+
+    #[test]
+    fn test_verdict_columns() {
+        let mut results = results();
+        results[0].part1_verdict = Some(Verdict::Correct);
+        results[0].part2_verdict = Some(Verdict::Wrong { expected: 32.into() });
+        results[1].part2_verdict = Some(Verdict::Unknown);
+        let expected = "\
+day,title,part1,part2,part1_check,part2_check,parse_us,part1_us,part2_us,total_us
+1,Historian Hysteria,11,31,correct,\"wrong, expected 32\",20,3,5,28
+7,\"Bridge \"\"Repair\"\"\",,\"1,2|3\",,unknown,100,0,1234567,1234667
+";
+        assert_eq!(render(&results, Format::Csv, None), expected);
+        assert!(render(&results, Format::Json, None).contains("\"part1_check\": null, \"part2_check\": \"unknown\""));
+    }
+
+    #[test]
+    fn test_wall_clock() {
+        let wall_clock = Some(Duration::from_micros(617347));
+        let text = render(&results(), Format::Text, wall_clock);
+        assert!(text.ends_with(" 1234695\nWall clock 617347 μs, sum of days 1234695 μs (2.0x)\n"));
+        let json = render(&results(), Format::Json, wall_clock);
+        assert!(json.ends_with("\"total_us\": 1234695,\n  \"wall_clock_us\": 617347\n}\n"));
+        assert_eq!(render(&results(), Format::Csv, wall_clock), render(&results(), Format::Csv, None));
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("md".parse::<Format>(), Ok(Format::Markdown));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::cli::Part;
+use crate::error::{Error, Result};
+use crate::solution::Day;
+use crate::verify::{Expected, Verdict};
+
+/// Answers and per-phase timings of one day. Parts that were not selected are `None`
+/// and count as zero time. Verdicts are only set when the answers were verified.
+pub struct DayResult {
+    pub day: u32,
+    pub title: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub parse_time: Duration,
+    pub part1_time: Duration,
+    pub part2_time: Duration,
+    pub part1_verdict: Option<Verdict>,
+    pub part2_verdict: Option<Verdict>,
+}
+
+impl DayResult {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.part1_time + self.part2_time
+    }
+
+    /// Checks the answers against the expected ones, returns false if any answer is wrong.
+    pub fn verify(&mut self, expected: &Expected) -> bool {
+        self.part1_verdict = Verdict::check(&self.part1, &expected.part1);
+        self.part2_verdict = Verdict::check(&self.part2, &expected.part2);
+        ![&self.part1_verdict, &self.part2_verdict]
+            .iter()
+            .any(|v| matches!(v, Some(Verdict::Wrong { .. })))
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let now = Instant::now();
+    let result = f();
+    (result, now.elapsed())
+}
+
+/// Runs a day on input that has already been read into memory, so I/O is not part of the timings.
+/// A panic while parsing or solving is reported as that day's error so the other days still run.
+pub fn run_day(day: &Day, input: &[u8], part: Option<Part>) -> Result<DayResult> {
+    panic::catch_unwind(AssertUnwindSafe(|| run_day_unguarded(day, input, part))).unwrap_or_else(|e| Err(Error::from_panic(e)))
+}
+
+fn run_day_unguarded(day: &Day, input: &[u8], part: Option<Part>) -> Result<DayResult> {
+    let (parsed, parse_time) = timed(|| day.parse(&mut &input[..]));
+    let parsed = parsed?;
+    let (part1, part1_time) = match part {
+        None | Some(Part::One) => {
+            let (answer, time) = timed(|| day.part1(&parsed));
+            (Some(answer), time)
+        }
+        Some(Part::Two) => (None, Duration::ZERO),
+    };
+    let (part2, part2_time) = match part {
+        None | Some(Part::Two) => {
+            let (answer, time) = timed(|| day.part2(&parsed));
+            (Some(answer), time)
+        }
+        Some(Part::One) => (None, Duration::ZERO),
+    };
+    Ok(DayResult {
+        day: day.day,
+        title: day.title,
+        part1,
+        part2,
+        parse_time,
+        part1_time,
+        part2_time,
+        part1_verdict: None,
+        part2_verdict: None,
+    })
+}
+
+/// Runs the days on a pool of worker threads and returns their results in the order of `days`.
+/// A day never moves between threads, so its phase timings still only cover its own work,
+/// but days compete for cores and caches and can be slower than in a sequential run.
+pub fn run_days_parallel(days: &[(&Day, Vec<u8>)], part: Option<Part>) -> Vec<Result<DayResult>> {
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(days.len());
+    let next = AtomicUsize::new(0);
+    let mut results = thread::scope(|scope| {
+        let workers = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((day, input)) = days.get(idx) else {
+                            return results;
+                        };
+                        results.push((idx, run_day(day, input, part)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use super::*;
+    use crate::solution::Solution;
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type Input = Vec<u32>;
+        const DAY: u32 = 1;
+        const TITLE: &'static str = "Panics";
+
+        fn parse(_: &mut dyn BufRead) -> Result<Self::Input> {
+            Ok(vec![])
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input[0].into()
+        }
+
+        fn part2(_: &Self::Input) -> Answer {
+            0.into()
+        }
+    }
+
+    #[test]
+    fn test_panic_fails_only_the_day() {
+        let day = Day::of::<Panics>();
+        let err = run_day(&day, b"", None).err().unwrap();
+        assert!(matches!(err, Error::Panic(_)));
+        assert!(err.to_string().starts_with("panicked: index out of bounds"));
+        assert_eq!(run_day(&day, b"", Some(Part::Two)).unwrap().part2, Some(0.into()));
+
+        let results = run_days_parallel(&[(&day, vec![]), (&day, vec![])], None);
+        assert!(results.iter().all(|r| r.is_err()));
+    }
+}
@@ -0,0 +1,168 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Module written for a new day, `{day}` is replaced with the zero padded day,
+/// `{DAY}` with the plain day number and `{title}` with the puzzle title.
+const TEMPLATE: &str = r#"use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{self, Result};
+use crate::solution::Solution;
+
+fn parse_data<R: BufRead>(reader: R) -> Result<Vec<String>> {
+    error::lines(reader).map(|line| line.map(|(_, line)| line)).collect()
+}
+
+fn part1(data: &[String]) -> usize {
+    data.len()
+}
+
+fn part2(data: &[String]) -> usize {
+    data.len()
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+    const DAY: u32 = {DAY};
+    const TITLE: &'static str = "{title}";
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_data(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+    use super::*;
+    use crate::days::open_input;
+
+    const TEST: &str = "\
+";
+
+    #[test]
+    fn test_part1() {
+        let input_file = BufReader::new(TEST.as_bytes());
+        let data = parse_data(input_file).unwrap();
+        assert_eq!(part1(&data), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input_file = BufReader::new(TEST.as_bytes());
+        let data = parse_data(input_file).unwrap();
+        assert_eq!(part2(&data), 0);
+    }
+
+    #[test]
+    fn test_sol() {
+        assert_eq!((0.into(), 0.into()), Day{day}::solve(&mut open_input({DAY})))
+    }
+}
+"#;
+
+fn render(day: u32, title: &str) -> String {
+    TEMPLATE
+        .replace("{day}", &format!("{:02}", day))
+        .replace("{DAY}", &day.to_string())
+        .replace("{title}", &title.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Adds `dayNN => DayNN` to the `register_days!` list in `days/mod.rs`, keeping it sorted.
+fn register(mod_rs: &str, day: u32) -> Result<String, String> {
+    let entry = format!("day{0:02} => Day{0:02},", day);
+    let start = mod_rs.find("register_days! {").ok_or("no `register_days!` list in days/mod.rs")?;
+    let end = start + mod_rs[start..].find("\n}").ok_or("unterminated `register_days!` list in days/mod.rs")?;
+    let mut entries = mod_rs[start..end].lines().skip(1).map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>();
+    if entries.contains(&entry.as_str()) {
+        return Err(format!("day {:02} is already registered", day));
+    }
+    entries.push(&entry);
+    entries.sort();
+    let list = entries.iter().map(|e| format!("    {}\n", e)).collect::<String>();
+    Ok(format!("{}register_days! {{\n{}{}", &mod_rs[..start], list.trim_end_matches('\n'), &mod_rs[end..]))
+}
+
+/// Creates `src/days/dayNN.rs`, registers it and creates an empty `input/NN.txt` below `root`.
+/// Returns the files that were written, an existing input file is left alone.
+pub fn new_day(root: &Path, day: u32, title: &str) -> io::Result<Vec<PathBuf>> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    if !(1..=25).contains(&day) {
+        return Err(invalid(format!("day {} is not between 1 and 25", day)));
+    }
+    let module = root.join(format!("src/days/day{:02}.rs", day));
+    if module.exists() {
+        return Err(invalid(format!("{} already exists", module.display())));
+    }
+    let mod_rs = root.join("src/days/mod.rs");
+    let registered = register(&fs::read_to_string(&mod_rs)?, day).map_err(invalid)?;
+
+    fs::write(&module, render(day, title))?;
+    fs::write(&mod_rs, registered)?;
+    let mut written = vec![module, mod_rs];
+    let input = root.join(format!("input/{:02}.txt", day));
+    if !input.exists() {
+        fs::create_dir_all(root.join("input"))?;
+        fs::write(&input, "")?;
+        written.push(input);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "\
+register_days! {
+    day01 => Day01,
+    day11 => Day11,
+}
+
+pub mod grid;
+";
+
+    #[test]
+    fn test_register() {
+        let registered = register(MOD_RS, 3).unwrap();
+        assert_eq!(registered, MOD_RS.replace("Day01,\n", "Day01,\n    day03 => Day03,\n"));
+        assert_eq!(register(&registered, 12).unwrap(), registered.replace("Day11,\n", "Day11,\n    day12 => Day12,\n"));
+        assert!(register(MOD_RS, 11).is_err());
+        assert!(register("pub mod grid;", 3).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let module = render(7, "Say \"hi\"");
+        assert!(module.contains("pub struct Day07;"));
+        assert!(module.contains("const DAY: u32 = 7;"));
+        assert!(module.contains("const TITLE: &'static str = \"Say \\\"hi\\\"\";"));
+        assert!(module.contains("Day07::solve(&mut open_input(7))"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), MOD_RS).unwrap();
+
+        let written = new_day(&root, 12, "Garden Groups").unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(fs::read_to_string(root.join("src/days/mod.rs")).unwrap().contains("day12 => Day12,"));
+        assert_eq!(fs::read_to_string(root.join("input/12.txt")).unwrap(), "");
+        assert!(new_day(&root, 12, "Garden Groups").is_err());
+        assert!(new_day(&root, 26, "").is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
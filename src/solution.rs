@@ -0,0 +1,75 @@
+use std::any::Any;
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::Result;
+
+/// A puzzle solution split into its parsing step and the two parts.
+/// Days register an implementation in `days/mod.rs` and the runner picks them up from the registry.
+pub trait Solution {
+    type Input: 'static;
+
+    const YEAR: u32 = 2024;
+    const DAY: u32;
+    const TITLE: &'static str;
+
+    fn parse(input: &mut dyn BufRead) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+
+    /// Parses and runs both parts in one go, used by the per-day solution tests.
+    #[cfg(test)]
+    fn solve(input: &mut dyn BufRead) -> (Answer, Answer) {
+        let input = Self::parse(input).unwrap_or_else(|e| panic!("{}", e));
+        (Self::part1(&input), Self::part2(&input))
+    }
+}
+
+/// Parsed input of a registered day, only usable with the `Day` that produced it.
+pub type Parsed = Box<dyn Any>;
+
+/// Type erased `Solution` so days with different input types can live in the same registry.
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    parse: fn(&mut dyn BufRead) -> Result<Parsed>,
+    part1: fn(&Parsed) -> Answer,
+    part2: fn(&Parsed) -> Answer,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Day {
+        fn parse<S: Solution>(input: &mut dyn BufRead) -> Result<Parsed> {
+            Ok(Box::new(S::parse(input)?))
+        }
+        fn part1<S: Solution>(input: &Parsed) -> Answer {
+            S::part1(input.downcast_ref().expect("input parsed by another day"))
+        }
+        fn part2<S: Solution>(input: &Parsed) -> Answer {
+            S::part2(input.downcast_ref().expect("input parsed by another day"))
+        }
+        Day {
+            year: S::YEAR,
+            day: S::DAY,
+            title: S::TITLE,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed> {
+        (self.parse)(input)
+    }
+
+    pub fn part1(&self, input: &Parsed) -> Answer {
+        (self.part1)(input)
+    }
+
+    pub fn part2(&self, input: &Parsed) -> Answer {
+        (self.part2)(input)
+    }
+}
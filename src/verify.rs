@@ -0,0 +1,125 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::answer::Answer;
+use crate::cli::InputSource;
+
+/// Known answers for one input, stored next to it with an `.answers` extension
+/// (`input/06.txt` -> `input/06.answers`) as `part1: <answer>` and `part2: <answer>` lines.
+/// Either line may be missing when the answer isn't known yet.
+#[derive(Debug, Default, PartialEq)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Expected {
+    pub fn path_for(source: &InputSource) -> Option<PathBuf> {
+        match source {
+            InputSource::File(path) => Some(path.with_extension("answers")),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Loads the answers for an input, a missing file means nothing is known about it.
+    pub fn load(source: &InputSource) -> io::Result<Expected> {
+        let Some(path) = Expected::path_for(source) else {
+            return Ok(Expected::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => Expected::parse(&text).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Expected::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Expected, String> {
+        let mut expected = Expected::default();
+        for (idx, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected `part1: <answer>` on line {}", idx + 1))?;
+            let answer = Some(Answer::from(value.trim()));
+            match key.trim() {
+                "part1" => expected.part1 = answer,
+                "part2" => expected.part2 = answer,
+                key => return Err(format!("unknown key `{}` on line {}", key, idx + 1)),
+            }
+        }
+        Ok(expected)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: Answer },
+    Unknown,
+}
+
+impl Verdict {
+    /// `None` when the part wasn't run.
+    pub fn check(actual: &Option<Answer>, expected: &Option<Answer>) -> Option<Verdict> {
+        match (actual, expected) {
+            (None, _) => None,
+            (Some(_), None) => Some(Verdict::Unknown),
+            (Some(actual), Some(expected)) if actual == expected => Some(Verdict::Correct),
+            (Some(_), Some(expected)) => Some(Verdict::Wrong { expected: expected.clone() }),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong { .. } => "wrong",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Wrong { expected } => write!(f, "wrong, expected {}", expected),
+            verdict => write!(f, "{}", verdict.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let expected = Expected::parse("part1: 4883\npart2: a,b,c\n").unwrap();
+        assert_eq!(expected.part1, Some(4883.into()));
+        assert_eq!(expected.part2, Some("a,b,c".into()));
+
+        let expected = Expected::parse("part2: 7").unwrap();
+        assert_eq!(expected.part1, None);
+
+        assert!(Expected::parse("part3: 1").is_err());
+        assert!(Expected::parse("4883").is_err());
+    }
+
+    #[test]
+    fn test_path_for() {
+        let source = InputSource::File(PathBuf::from("input/team/06.txt"));
+        assert_eq!(Expected::path_for(&source), Some(PathBuf::from("input/team/06.answers")));
+        assert_eq!(Expected::path_for(&InputSource::Stdin), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let answer = Some(Answer::from(41usize));
+        assert_eq!(Verdict::check(&answer, &Some("41".into())), Some(Verdict::Correct));
+        assert_eq!(Verdict::check(&answer, &Some(42.into())), Some(Verdict::Wrong { expected: 42.into() }));
+        assert_eq!(Verdict::check(&answer, &None), Some(Verdict::Unknown));
+        assert_eq!(Verdict::check(&None, &Some(42.into())), None);
+    }
+}